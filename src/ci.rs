@@ -0,0 +1,249 @@
+//! CI pipelines, see the `--ci` flag and the `Ci` stage.
+//! The pipeline has a job for the project, or one for each of its parts (`ProjectType::parts`), which sets up the
+//! project type's runtimes (`ProjectType::runtimes`) at the versions found by the tooling checks, then runs the same
+//! setup, lint, test and build commands the README lists (`ProjectType::dev_commands`).
+
+use crate::{
+    data::{Flag, ProjectType},
+    pipeline::{DevCommands, ProjectFile, SetUp},
+};
+
+/// A job of the pipeline, run in `dir` (relative to the project root) if it's for a part of the project.
+struct Job {
+    name: &'static str,
+    dir: Option<&'static str>,
+    project_type: ProjectType,
+    commands: DevCommands,
+}
+
+/// The pipeline file for `provider`, one of `VALID_CI_PROVIDERS`.
+pub fn file(set_up: &SetUp, provider: &str) -> ProjectFile {
+    let project_type = set_up.project_type;
+    let jobs: Vec<Job> = if project_type.parts().is_empty() {
+        vec![Job {
+            name: "ci",
+            dir: None,
+            project_type,
+            commands: project_type.dev_commands(set_up),
+        }]
+    } else {
+        project_type
+            .parts()
+            .iter()
+            .map(|(folder, part_type)| Job {
+                name: folder,
+                dir: Some(folder),
+                project_type: *part_type,
+                commands: part_type.dev_commands(set_up),
+            })
+            .collect()
+    };
+
+    match provider {
+        "gitlab" => ProjectFile::new(".gitlab-ci.yml", gitlab_pipeline(set_up, &jobs)),
+        _ => ProjectFile::new(".github/workflows/ci.yml", github_workflow(set_up, &jobs)),
+    }
+}
+
+fn github_workflow(set_up: &SetUp, jobs: &[Job]) -> String {
+    let branch = set_up.config.get("git.default_branch").unwrap_or("main");
+    let mut workflow =
+        format!("name: CI\n\non:\n  push:\n    branches: [{branch}]\n  pull_request:\n\njobs:\n");
+
+    for job in jobs {
+        workflow.push_str(&format!("  {}:\n    runs-on: ubuntu-latest\n", job.name));
+        if let Some(dir) = job.dir {
+            workflow.push_str(&format!(
+                "    defaults:\n      run:\n        working-directory: {dir}\n"
+            ));
+        }
+        workflow.push_str("    steps:\n      - uses: actions/checkout@v4\n");
+
+        for runtime in job.project_type.runtimes() {
+            workflow.push_str(&match *runtime {
+                "python" => format!(
+                    "      - uses: actions/setup-python@v5\n        with:\n          python-version: \"{}\"\n",
+                    set_up.tool_versions.pinned_or("python", 2, "3")
+                ),
+                "node" => format!(
+                    "      - uses: actions/setup-node@v4\n        with:\n          node-version: \"{}\"\n",
+                    set_up.tool_versions.pinned_or("node", 1, "lts/*")
+                ),
+                "go" => format!(
+                    "      - uses: actions/setup-go@v5\n        with:\n          go-version: \"{}\"\n",
+                    set_up.tool_versions.pinned_or("go", 2, "stable")
+                ),
+                _ => {
+                    let toolchain = set_up.tool_versions.pinned_or("rustc", 3, "stable");
+                    github_step(
+                        "Set up rust",
+                        &[format!("rustup toolchain install {toolchain} --profile minimal --component clippy && rustup default {toolchain}")],
+                    )
+                }
+            });
+        }
+        for (name, script) in steps(set_up, job, "sudo ") {
+            workflow.push_str(&github_step(name, &script));
+        }
+    }
+
+    workflow
+}
+
+fn github_step(name: &str, script: &[String]) -> String {
+    match script {
+        [cmd] if is_plain(cmd) => format!("      - name: {name}\n        run: {cmd}\n"),
+        _ => format!(
+            "      - name: {name}\n        run: |\n{}",
+            script
+                .iter()
+                .map(|cmd| format!("          {cmd}\n"))
+                .collect::<String>()
+        ),
+    }
+}
+
+fn gitlab_pipeline(set_up: &SetUp, jobs: &[Job]) -> String {
+    let mut pipeline = vec![];
+
+    for job in jobs {
+        let (runtime, other_runtimes) = job
+            .project_type
+            .runtimes()
+            .split_first()
+            .unwrap_or((&"node", &[]));
+        let image = match *runtime {
+            "python" => format!(
+                "python:{}",
+                set_up.tool_versions.pinned_or("python", 2, "3")
+            ),
+            "node" => format!("node:{}", set_up.tool_versions.pinned_or("node", 1, "lts")),
+            "go" => format!("golang:{}", set_up.tool_versions.pinned_or("go", 2, "1")),
+            _ => format!("rust:{}", set_up.tool_versions.pinned_or("rustc", 3, "1")),
+        };
+
+        let mut script = vec![];
+        if let Some(dir) = job.dir {
+            script.push(format!("cd {dir}"));
+        }
+        if *runtime == "rust" {
+            script.push(String::from("rustup component add clippy"));
+        }
+        // the images only come with their own runtime, node is the only one added to another's
+        if other_runtimes.contains(&"node") {
+            let major = set_up.tool_versions.pinned_or("node", 1, "lts");
+            script.extend([
+                format!("curl -fsSL https://deb.nodesource.com/setup_{major}.x | bash -"),
+                String::from("apt-get install -y nodejs"),
+            ]);
+        }
+        // the jobs' scripts are run in a single shell, so the virtual environment stays activated
+        let mut activated = false;
+        for (_, step) in steps(set_up, job, "") {
+            for cmd in step {
+                if cmd == ". env/bin/activate" {
+                    if activated {
+                        continue;
+                    }
+                    activated = true;
+                }
+                script.push(cmd);
+            }
+        }
+
+        let script: String = script
+            .iter()
+            .map(|cmd| match is_plain(cmd) {
+                true => format!("    - {cmd}\n"),
+                false => format!("    - '{}'\n", cmd.replace('\'', "''")),
+            })
+            .collect();
+        pipeline.push(format!(
+            "{}:\n  image: {image}\n  script:\n{script}",
+            job.name
+        ));
+    }
+
+    pipeline.join("\n")
+}
+
+/// The job's steps after its runtimes are set up, by name, as the lines of shell they run. `sudo` prefixes the
+/// commands that need root, for runners that don't run jobs as root.
+fn steps(set_up: &SetUp, job: &Job, sudo: &str) -> Vec<(&'static str, Vec<String>)> {
+    let commands = &job.commands;
+    let activate: Vec<String> = match commands.venv {
+        true => vec![String::from(". env/bin/activate")],
+        false => vec![],
+    };
+    let mut steps = vec![];
+
+    let packages = job.project_type.system_packages();
+    if !packages.is_empty() {
+        steps.push((
+            "Install system packages",
+            vec![format!(
+                "{sudo}apt-get update && {sudo}apt-get install -y --no-install-recommends {}",
+                packages.join(" ")
+            )],
+        ));
+    }
+
+    let mut install = vec![];
+    if job.project_type.runtimes().contains(&"node") {
+        if let Some(cmd) = Flag::get_package_manager(set_up.flags).setup_cmd() {
+            install.push(cmd.to_string());
+        }
+    }
+    if commands.venv {
+        install.push(String::from("python -m venv env"));
+        install.extend(activate.clone());
+    }
+    install.extend(commands.setup.iter().cloned());
+    if !install.is_empty() {
+        steps.push(("Install dependencies", install));
+    }
+
+    for (name, cmd) in [
+        ("Lint", &commands.lint),
+        ("Test", &commands.test),
+        ("Build", &commands.build),
+    ] {
+        if let Some(cmd) = cmd {
+            steps.push((name, [activate.clone(), vec![cmd.clone()]].concat()));
+        }
+    }
+
+    steps
+}
+
+/// Whether `cmd` can be written as a plain YAML scalar, without quotes.
+fn is_plain(cmd: &str) -> bool {
+    !cmd.contains(": ")
+        && !cmd.contains(" #")
+        && !cmd.starts_with([
+            '\'', '"', '&', '*', '!', '|', '>', '%', '@', '`', '{', '[', '-',
+        ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pipelines_have_a_job_per_part_running_its_commands() {
+        let set_up = SetUp::new(ProjectType::Fullstack, &[]);
+
+        let workflow = file(&set_up, "github");
+        assert_eq!(workflow.path, ".github/workflows/ci.yml");
+        assert!(workflow.contents.contains("  backend:\n    runs-on: ubuntu-latest\n    defaults:\n      run:\n        working-directory: backend\n"));
+        assert!(workflow.contents.contains("      - name: Test\n        run: |\n          . env/bin/activate\n          python manage.py test\n"));
+        assert!(workflow
+            .contents
+            .contains("      - name: Lint\n        run: npm run lint\n"));
+
+        let pipeline = file(&set_up, "gitlab");
+        assert_eq!(pipeline.path, ".gitlab-ci.yml");
+        assert!(pipeline.contents.contains("backend:\n  image: python:3\n  script:\n    - cd backend\n    - python -m venv env\n    - . env/bin/activate\n    - pip install django django-cors-headers\n    - python manage.py test\n"));
+        assert!(pipeline.contents.contains("frontend:\n  image: node:lts\n"));
+    }
+}
@@ -0,0 +1,115 @@
+//! User configuration, defaults that would otherwise be prompted for on every set-up.
+//! Read from `$PLATEBOILER_CONFIG` if set, otherwise `plateboiler/config.toml` in the user's config directory
+//! (`$XDG_CONFIG_HOME` or `~/.config`, `%APPDATA%` on windows). Only a subset of TOML is understood:
+//! `[section]` headers, and `key = "value"` lines, read as `section.key`. A missing file is an empty configuration.
+//!
+//! ```toml
+//! [go]
+//! module_prefix = "github.com/me"
+//! ```
+
+use std::{collections::HashMap, env, fs, path::PathBuf};
+
+use crate::utils::yellow_log;
+
+#[derive(Debug, Default, Clone)]
+pub struct Config {
+    values: HashMap<String, String>,
+}
+
+impl Config {
+    /// Loads the user's configuration, warning about (and skipping) lines that can't be read.
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        let Ok(contents) = fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        let (config, bad_lines) = Self::parse(&contents);
+        for line in bad_lines {
+            yellow_log(format!("ignored line {line} of config file {path:?}").as_str());
+        }
+        config
+    }
+
+    /// The value of `key`, written `section.key` for keys in a section.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os("PLATEBOILER_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+
+        let config_dir = if cfg!(windows) {
+            PathBuf::from(env::var_os("APPDATA")?)
+        } else {
+            match env::var_os("XDG_CONFIG_HOME") {
+                Some(dir) => PathBuf::from(dir),
+                None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+            }
+        };
+        Some(config_dir.join("plateboiler").join("config.toml"))
+    }
+
+    /// Parses `contents`, returning the configuration and the (1 based) numbers of the lines that couldn't be read.
+    fn parse(contents: &str) -> (Self, Vec<usize>) {
+        let mut config = Self::default();
+        let mut bad_lines = vec![];
+        let mut section = String::new();
+
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name.trim().to_string();
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                bad_lines.push(i + 1);
+                continue;
+            };
+            let value = value.trim();
+            let Some(value) = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            else {
+                bad_lines.push(i + 1);
+                continue;
+            };
+
+            let key = match section.as_str() {
+                "" => key.trim().to_string(),
+                section => format!("{section}.{}", key.trim()),
+            };
+            config.values.insert(key, value.to_string());
+        }
+
+        (config, bad_lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_prefix_keys_and_bad_lines_are_reported() {
+        let (config, bad_lines) = Config::parse(
+            "# defaults\nauthor = \"Ada\"\n\n[go]\nmodule_prefix = 'github.com/ada'\nnot a setting\nnumber = 3\n",
+        );
+
+        assert_eq!(config.get("author"), Some("Ada"));
+        assert_eq!(config.get("go.module_prefix"), Some("github.com/ada"));
+        assert_eq!(config.get("module_prefix"), None);
+        assert_eq!(bad_lines, vec![6, 7]);
+    }
+}
@@ -7,20 +7,115 @@ type LongForm = &'static str;
 
 /// The only project options you can pass to the CLI, along with their corresponding ProjectType enums, and descriptions.
 /// `option = (option, ProjectType, description)`.
-pub const VALID_PROJECT_OPTIONS: [(Name, ProjectType, Description); 3] = [
-    ("django", ProjectType::Django, "Python Django web-framework project. Requires Python version 3. Sets up a virtual environment 'venv' using the standard venv module; Installs Django into venv using pip; Starts a Django project 'core'; Runs the Django dev server."), 
-    ("web", ProjectType::Web,"Javascript (or TS) Web-app project with Vite (React/Vue/Angular etc). Currently requires/uses Node.js. Uses NPM and Vite CLI to set up a Web project with further configurations prompted to user (piped from Vite CLI). Installs node modules and runs the Vite dev server"), 
-    ("next", ProjectType::Next,"Javascript (or TS) Next web-framework project. Currently requires/uses Node.js. Uses NPM and Next CLI to set up a Next project with further configurations prompted to user (piped from Next CLI). Runs the Next dev server")
+pub const VALID_PROJECT_OPTIONS: [(Name, ProjectType, Description); 16] = [
+    ("django", ProjectType::Django, "Python Django web-framework project. Requires Python version 3. Sets up a virtual environment 'venv' using the standard venv module; Installs Django into venv using pip; Starts a Django project in a 'core' package (see --django-package) and the apps passed with --apps, registered in INSTALLED_APPS. Extras (--extras): 'custom-user' (a 'users' app with a User model set as AUTH_USER_MODEL, and its initial migration, made before the database is first migrated). Runs the Django dev server."), 
+    ("web", ProjectType::Web,"Javascript (or TS) Web-app project with Vite (React/Vue/Angular etc). Currently requires/uses Node.js. Uses NPM and Vite CLI to set up a Web project with further configurations prompted to user (piped from Vite CLI), or its react-ts template with --yes. Installs node modules and runs the Vite dev server"), 
+    ("next", ProjectType::Next,"Javascript (or TS) Next web-framework project. Currently requires/uses Node.js. Uses NPM and Next CLI to set up a Next project with further configurations prompted to user (piped from Next CLI), or its defaults with --yes. Runs the Next dev server"),
+    ("rust", ProjectType::Rust, "Rust project with Cargo. Requires cargo and rustc (see https://rustup.rs). Variants (--variant): 'bin' (default) and 'lib' use cargo init, 'workspace' writes a workspace with a binary crate and a library crate under crates/. Extras (--extras): 'clippy' (clippy.toml), 'rustfmt' (rustfmt.toml) and 'test' (an example integration test). Runs the binary with cargo run, if there is one."),
+    ("fastapi", ProjectType::FastApi, "Python FastAPI web-framework project. Requires Python version 3. Writes an 'app' package (app/main.py with the routes, app/settings.py reading settings from environment variables), a pytest test and a requirements.txt; Sets up a virtual environment 'env' and installs FastAPI, uvicorn, httpx and pytest into it using pip; Runs the uvicorn dev server."),
+    ("flask", ProjectType::Flask, "Python Flask web-framework project. Requires Python version 3. Writes an 'app' package (an app factory in app/__init__.py, per environment settings in app/settings.py picked by APP_ENV), wsgi.py for the waitress WSGI server, a pytest test and a requirements.txt; Sets up a virtual environment 'env' and installs Flask, waitress and pytest into it using pip; Runs the Flask dev server."),
+    ("node-api", ProjectType::NodeApi, "Javascript (or TS) Node.js backend project. Currently requires/uses Node.js. Variants (--variant): 'express' (default) and 'fastify'. Extras (--extras): 'typescript' (tsconfig.json, tsx for the dev and test scripts, tsc for the build script). Writes the app with a /health route and a test for it using the node test runner; Installs the framework with npm; Runs the dev server with file watching."),
+    ("go", ProjectType::Go, "Go module project. Requires go (see https://go.dev/doc/install). Creates the module with go mod init, its module path being the go.module_prefix config setting joined with the project name, or prompted for; Writes cmd/<name>/main.go and an internal/app package with a test; Runs the program with go run."),
+    ("sveltekit", ProjectType::SvelteKit, "Javascript (or TS) SvelteKit web-framework project. Currently requires/uses Node.js. Uses the sv CLI (sv create) to set up a SvelteKit project with further configurations prompted to user, or its minimal typescript template with --yes. Installs node modules and runs the Vite dev server"),
+    ("nuxt", ProjectType::Nuxt, "Javascript (or TS) Nuxt (Vue) web-framework project. Currently requires/uses Node.js. Uses the nuxi CLI (nuxi init) to set up a Nuxt project with further configurations prompted to user, or its defaults with --yes. Installs node modules and runs the Nuxt dev server"),
+    ("astro", ProjectType::Astro, "Javascript (or TS) Astro web-framework project. Currently requires/uses Node.js. Uses the create-astro CLI to set up an Astro project with further configurations prompted to user, or its 'basics' template with --yes. Installs node modules and runs the Astro dev server"),
+    ("remix", ProjectType::Remix, "Javascript (or TS) Remix web-framework project. Currently requires/uses Node.js. Uses the create-remix CLI to set up a Remix project with further configurations prompted to user, or its defaults with --yes. Installs node modules and runs the Vite dev server"),
+    ("tauri", ProjectType::Tauri, "Javascript (or TS) desktop app with a Rust backend, using Tauri. Requires Node.js, cargo and rustc, and on linux the webkit2gtk system libraries (checked with pkg-config). Uses the create-tauri-app CLI to set up a Tauri project with further configurations prompted to user, or its vanilla-ts template with --yes. Installs node modules and runs the app with tauri dev"),
+    ("electron", ProjectType::Electron, "Javascript desktop app using Electron. Currently requires/uses Node.js. Writes a main process (src/main), a preload script (src/preload) and a renderer page (src/renderer), with an electron-builder packaging config (electron-builder.yml, see the package and dist scripts). Installs electron and electron-builder and runs the app"),
+    ("fullstack", ProjectType::Fullstack, "Django backend with a Vite frontend in one project. Requires Python version 3 and Node.js. Sets up a django project in backend/ and a web project in frontend/, as the django and web project types do; Proxies /api and /admin from the Vite dev server to Django, and allows the Vite dev server's origin with django-cors-headers; Writes a root package.json whose dev script runs both dev servers at once with concurrently. Extras (--extras): 'custom-user', added to the django project as for django projects. The apps passed with --apps are created in the django project too."),
+    ("monorepo", ProjectType::Monorepo, "Javascript monorepo with npm, pnpm, yarn or bun workspaces (see --package-manager). Currently requires/uses Node.js. Variants (--variant): 'workspaces' (default, build and test scripts run in every package), 'turborepo' and 'nx' (dev, build and test scripts run with turbo or nx). Writes the workspace root, with an apps/ folder and shared packages/ui and packages/config (a base tsconfig) packages; Installs the workspace. Add apps to it from inside it with `add app <web|next|node-api>`.")
     ];
 
 /// The only flags you can pass to the CLI, along with their short forms, corresponding Flag enums, and descriptions.
 /// Some flags only have an effect when passed with certain options. In these cases other non compatible flags will be completely egnored.
 /// `flag = (long_form, short_form, Flag, description)`.
-pub const VALID_FLAGS: [(LongForm, ShortForm, Flag, Description); 4] = [
+pub const VALID_FLAGS: [(LongForm, ShortForm, Flag, Description); 26] = [
     ("--help", "-h", Flag::Help, "Show CLI help. If passed with an option, shows option description and optional flags with their descriptions."),
     ("--verbose", "-v", Flag::Verbose, "Show all CLI output."),
     ("--name", "-n", Flag::Name(Value(None)), "Set name of project (--name=<project_name>)."),
-    ("--test", "-t", Flag::Test, "Set the target directory of the project folder to <currrent-directory>/test_runs.")
+    ("--test", "-t", Flag::Test, "Set the target directory of the project folder to <currrent-directory>/test_runs. Ignored if --dir is passed."),
+    ("--no-run", "-nr", Flag::NoRun, "Skip running the dev server once the project is set up."),
+    ("--run", "-r", Flag::Run(Value(None)), "Set how the dev server is run once the project is set up (--run=<foreground|background>). 'foreground' (default) blocks until you press Ctrl-C, 'background' starts it detached, waits for its port to answer, prints the URL and exits."),
+    ("--timeout", "-to", Flag::Timeout(Value(None)), "Set the timeout in seconds for each attempt of a network-bound step such as installing dependencies, 0 for none (--timeout=<seconds>). Defaults depend on the project type."),
+    ("--retries", "-rt", Flag::Retries(Value(None)), "Set how many times a failed network-bound step is retried (--retries=<count>). Defaults depend on the project type."),
+    ("--backoff", "-bo", Flag::Backoff(Value(None)), "Set the wait in seconds before the first retry of a network-bound step, doubled after every further failure (--backoff=<seconds>). Defaults depend on the project type."),
+    ("--dir", "-d", Flag::Dir(Value(None)), "Set the target directory the project folder is created in, creating it (and its parents) if needed (--dir=<path> or --dir <path>). Defaults to the current directory."),
+    ("--force", "-f", Flag::Force(Value(None)), "Allow an existing project folder (--force or --force=overwrite). Without a value the folder must be empty, with 'overwrite' a non-empty folder is cleared after confirmation."),
+    ("--variant", "-vr", Flag::Variant(Value(None)), "Set the project variant, for project types that have several (--variant=<variant>). Prompted for if not passed, see the project type's help."),
+    ("--extras", "-x", Flag::Extras(Value(None)), "Set the optional extras to add, for project types that have any (--extras=<extra>,<extra> or --extras=none). Prompted for if not passed, see the project type's help."),
+    ("--package-manager", "-pm", Flag::PackageManager(Value(None)), "Set the package manager node based projects are created, installed and run with (--package-manager=<npm|pnpm|yarn|bun>). Defaults to npm."),
+    ("--yes", "-y", Flag::Yes, "Accept defaults instead of prompting: the project type's default variant, no extras, and the defaults of the create CLIs used by web, next, sveltekit, nuxt, astro and remix projects. The project name is still prompted for if --name is not passed."),
+    ("--git", "-g", Flag::Git, "Initialise a git repository in the project even if it is inside an existing one, which is skipped otherwise. The default branch is the git.default_branch config setting, or 'main'."),
+    ("--no-git", "-ng", Flag::NoGit, "Skip initialising a git repository and making the initial commit. The .gitignore is still written."),
+    ("--open", "-o", Flag::Open(Value(None)), "Open the project once it is set up, before the dev server is started (--open or --open=<editor|code|idea|files|none>). 'editor' is $VISUAL or $EDITOR, 'files' the file manager (xdg-open, explorer on windows). Without a value the first of these that is available is used. Defaults to the open.with config setting, 'none' overriding it."),
+    ("--license", "-l", Flag::License(Value(None)), "Write a LICENSE file for the given license, and set it as the license in the project's package.json, Cargo.toml or pyproject.toml (--license=<spdx-id>). One of: MIT, Apache-2.0, GPL-2.0-only, GPL-2.0-or-later, GPL-3.0-only, GPL-3.0-or-later, LGPL-3.0-only, LGPL-3.0-or-later, BSD-2-Clause, BSD-3-Clause, ISC, MPL-2.0 and Unlicense. The copyright holder is the author config setting, or your git user.name, or prompted for."),
+    ("--docker", "-dk", Flag::Docker(Value(None)), "Add a multi-stage Dockerfile, a .dockerignore and a compose.yaml for the project (--docker or --docker=postgres). Django apps are served by gunicorn, static sites (web, astro) by nginx, and next apps from their standalone output. 'postgres' adds a Postgres service to the compose.yaml of django and fullstack projects, and points django at it. Not available for desktop apps, rust libraries and monorepos. Docker itself is not needed to write them."),
+    ("--devcontainer", "-dc", Flag::DevContainer, "Add a .devcontainer/devcontainer.json for the project, with the versions of python, node, go or rust found on this machine, the dev server's ports forwarded, the setup commands run once the container is created, and the recommended VS Code extensions. Tauri apps also get a .devcontainer/Dockerfile installing the system libraries they build against."),
+    ("--ci", "-ci", Flag::Ci(Value(None)), "Add a CI pipeline for the project (--ci=<github|gitlab>), written to .github/workflows/ci.yml or .gitlab-ci.yml. It installs the dependencies the way the project is set up, with the package manager picked or in a python virtual environment, then lints, tests and builds it as its README describes, with python, node, go or rust pinned to the versions found on this machine."),
+    ("--lint", "-li", Flag::Lint(Value(None)), "Set up linting and formatting (--lint or --lint=<eslint|biome>), with an .editorconfig for every project type. Web and next projects get ESLint and Prettier, or Biome, run on staged files before each commit by husky and lint-staged. Django, fastapi and flask projects get ruff and black, configured in pyproject.toml and run by pre-commit. Settings are merged into the config files the project was generated with, and the project is formatted once."),
+    ("--testing", "-te", Flag::Testing(Value(None)), "Add a test setup with a passing example test (--testing or --testing=run). Web projects get Vitest and Testing Library (skipped with a warning if the vite app has no src/App.tsx), next projects Playwright end-to-end tests, and django projects pytest with pytest-django, wired into the test script or pyproject.toml. With 'run', the tests are run once to confirm they pass."),
+    ("--django-package", "-dp", Flag::DjangoPackage(Value(None)), "Set the package django's settings, urls and wsgi modules are created in by startproject (--django-package or --django-package=<package>). Without a value it is named after the project. Defaults to the django.package config setting, or 'core'."),
+    ("--apps", "-ap", Flag::Apps(Value(None)), "Create apps in a django project, or the django part of a fullstack one, with startapp, and add them to INSTALLED_APPS (--apps=<app>,<app>).")
 ];
 
-pub const CLI_HELP_TEXT_WITHOUT_PROJECT_NOR_FLAG_OPTION_DESCRIPTIONS: &str = "Plateboiler CLI HELP:\nThis CLI program helps setup various types of dev projects, think npm projects and the likes. For the time being. It will only setup web-app projects using npm and vite, as well as python projects.\nIt walks you through prompts asking for the type of project you want set up and any dependencies along with it, similar to more specific framework CLIs\n\nUSAGE:\nrun with: <project-type> <flags>";
+/// The ways the dev server can be run at the end of set-up, passed as values to the `--run` flag.
+pub const VALID_RUN_MODES: [&str; 2] = ["foreground", "background"];
+
+/// What the project can be opened with, passed as values to the `--open` flag or set as the `open.with` config setting.
+pub const VALID_OPENERS: [&str; 5] = ["editor", "code", "idea", "files", "none"];
+
+/// SPDX ids of the licenses bundled for the `--license` flag.
+pub const VALID_LICENSES: [&str; 13] = [
+    "MIT",
+    "Apache-2.0",
+    "GPL-2.0-only",
+    "GPL-2.0-or-later",
+    "GPL-3.0-only",
+    "GPL-3.0-or-later",
+    "LGPL-3.0-only",
+    "LGPL-3.0-or-later",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "ISC",
+    "MPL-2.0",
+    "Unlicense",
+];
+
+/// Values accepted by the `--docker` flag, besides passing it on its own.
+pub const VALID_DOCKER_SERVICES: [&str; 1] = ["postgres"];
+
+/// Where the CI pipeline can be run, passed as values to the `--ci` flag.
+pub const VALID_CI_PROVIDERS: [&str; 2] = ["github", "gitlab"];
+
+/// Lint presets of web and next projects, passed as values to the `--lint` flag. Defaults to eslint.
+pub const VALID_LINT_PRESETS: [&str; 2] = ["eslint", "biome"];
+
+/// Values accepted by the `--testing` flag, besides passing it on its own.
+pub const VALID_TESTING_MODES: [&str; 1] = ["run"];
+
+/// Values accepted by the `--force` flag, besides passing it on its own.
+pub const VALID_FORCE_MODES: [&str; 1] = ["overwrite"];
+
+/// Project types that can be added to a monorepo with `add app <project-type>`.
+pub const MONOREPO_APP_TYPES: [ProjectType; 3] =
+    [ProjectType::Web, ProjectType::Next, ProjectType::NodeApi];
+
+/// Package managers node based projects can be set up with, passed as values to the `--package-manager` flag.
+pub const VALID_PACKAGE_MANAGERS: [&str; 4] = ["npm", "pnpm", "yarn", "bun"];
+
+/// pkg-config names of the system libraries a tauri app builds against on linux.
+pub const TAURI_LINUX_PKG_CONFIG_PACKAGES: [&str; 5] = [
+    "webkit2gtk-4.1",
+    "javascriptcoregtk-4.1",
+    "libsoup-3.0",
+    "openssl",
+    "librsvg-2.0",
+];
+
+/// Exit code used when the user aborts the program with Ctrl-C (or SIGTERM), 128 + SIGINT as shells do.
+pub const ABORTED_EXIT_CODE: i32 = 130;
+
+/// How long to wait for a backgrounded dev server to start answering on its port, in seconds.
+pub const DEV_SERVER_STARTUP_TIMEOUT_SECS: u64 = 60;
+
+pub const CLI_HELP_TEXT_WITHOUT_PROJECT_NOR_FLAG_OPTION_DESCRIPTIONS: &str = "Plateboiler CLI HELP:\nThis CLI program helps setup various types of dev projects, think npm projects and the likes. For the time being. It will only setup web-app projects using npm and vite, as well as python projects.\nIt walks you through prompts asking for the type of project you want set up and any dependencies along with it, similar to more specific framework CLIs\n\nUSAGE:\nrun with: <project-type> <flags>\nor, from inside a monorepo: add app <web|next|node-api> <flags>";
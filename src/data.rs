@@ -3,16 +3,26 @@ use colored::*;
 use std::{
     env,
     fs::{self},
+    io::{self, IsTerminal},
     path::{Path, PathBuf},
     process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
 };
 
 use crate::{
     constants::{
-        CLI_HELP_TEXT_WITHOUT_PROJECT_NOR_FLAG_OPTION_DESCRIPTIONS, VALID_FLAGS,
-        VALID_PROJECT_OPTIONS,
+        CLI_HELP_TEXT_WITHOUT_PROJECT_NOR_FLAG_OPTION_DESCRIPTIONS,
+        DEV_SERVER_STARTUP_TIMEOUT_SECS, MONOREPO_APP_TYPES, TAURI_LINUX_PKG_CONFIG_PACKAGES,
+        VALID_CI_PROVIDERS, VALID_DOCKER_SERVICES, VALID_FLAGS, VALID_FORCE_MODES, VALID_LICENSES,
+        VALID_LINT_PRESETS, VALID_OPENERS, VALID_PACKAGE_MANAGERS, VALID_PROJECT_OPTIONS,
+        VALID_RUN_MODES, VALID_TESTING_MODES,
     },
-    utils::{self, blue_log, green_log, prompt_input, yellow_log, PEResult},
+    license,
+    naming::NamingRules,
+    pipeline::{self, SetUp},
+    signals,
+    utils::{self, blue_log, green_log, yellow_log, PEResult},
 };
 
 #[derive(PartialEq)]
@@ -24,6 +34,14 @@ pub enum DidSomething {
 #[derive(Debug)]
 pub struct ProgramError {
     message: String,
+    kind: ErrorKind,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ErrorKind {
+    Failed,
+    /// The user stopped the program, e.g. with Ctrl-C.
+    Aborted,
 }
 
 // IMPORTANT! update enum values in tandem with constants::VALID_PROJECT_OPTIONS
@@ -32,6 +50,19 @@ pub enum ProjectType {
     Django,
     Web,
     Next,
+    Rust,
+    FastApi,
+    Flask,
+    NodeApi,
+    Go,
+    SvelteKit,
+    Nuxt,
+    Astro,
+    Remix,
+    Tauri,
+    Electron,
+    Fullstack,
+    Monorepo,
 }
 
 // IMPORTANT! update enum values in tandem with constants::VALID_FLAGS
@@ -41,14 +72,98 @@ pub enum Flag {
     Verbose,
     Name(Value),
     Test,
+    NoRun,
+    Run(Value),
+    Timeout(Value),
+    Retries(Value),
+    Backoff(Value),
+    Dir(Value),
+    Force(Value),
+    Variant(Value),
+    Extras(Value),
+    PackageManager(Value),
+    Yes,
+    Git,
+    NoGit,
+    Open(Value),
+    License(Value),
+    Docker(Value),
+    DevContainer,
+    Ci(Value),
+    Lint(Value),
+    Testing(Value),
+    DjangoPackage(Value),
+    Apps(Value),
+}
+
+/// What may be done with an existing project folder, see the `--force` flag.
+#[derive(Debug, PartialEq)]
+pub enum ForceMode {
+    Off,
+    AllowEmpty,
+    Overwrite,
+}
+
+/// The javascript package manager used by node based project types, see the `--package-manager` flag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PackageManager {
+    Npm,
+    Pnpm,
+    Yarn,
+    Bun,
+}
+
+/// Versions of the tools found by `ProjectType::check_for_required_tooling`, e.g. `("node", "22.3.0")`, in the order
+/// they were checked.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ToolVersions(Vec<(&'static str, String)>);
+
+/// What the finished project is opened with, see the `--open` flag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Opener {
+    /// The first available of the others, in the order they are listed.
+    Detect,
+    /// `$VISUAL`, or `$EDITOR`.
+    Editor,
+    Code,
+    Idea,
+    FileManager,
+    Off,
+}
+
+/// How a non-interactive, usually network-bound, step is run: how long a single attempt may take, and how many times
+/// (waiting `backoff`, doubled after each failed attempt) it is retried.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RetryPolicy {
+    timeout: Option<Duration>,
+    retries: u32,
+    backoff: Duration,
+}
+
+/// How the dev server is run at the end of set-up, see the `--run` and `--no-run` flags.
+#[derive(Debug, PartialEq)]
+pub enum RunMode {
+    Foreground,
+    Background,
+    Skip,
 }
 
-struct Terminal {
-    working_dir: PathBuf,
+pub struct Terminal {
+    pub working_dir: PathBuf,
     base_shell_args: [String; 2],
 }
 
+/// What the program was asked to do with the project type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    /// Set up a new project, `<project-type> <flags>`.
+    SetUp,
+    /// Add an app to the monorepo the current directory is in, `add app <project-type> <flags>`.
+    AddApp,
+}
+
 pub struct ProgramArguments {
+    action: Action,
     project_type: Option<ProjectType>,
     flags: Vec<Flag>,
 }
@@ -63,16 +178,39 @@ impl Clone for Value {
 }
 
 impl ProgramArguments {
-    pub fn build<T: Iterator<Item = String>>(mut raw_args: T) -> PEResult<Self> {
+    pub fn build<T: Iterator<Item = String>>(raw_args: T) -> PEResult<Self> {
+        let mut action = Action::SetUp;
         let mut project_type: Option<ProjectType> = None;
         let mut flags: Vec<Flag> = vec![];
+        let mut raw_args = raw_args.map(|arg| arg.trim().to_string()).peekable();
 
-        while let Some(mut arg) = raw_args.next() {
-            arg = arg.trim().to_lowercase();
+        while let Some(arg) = raw_args.next() {
             if arg.starts_with("-") {
-                flags.push(Self::map_string_to_flag(arg)?);
+                let mut flag = Self::map_string_to_flag(arg.clone())?;
+                // flags taking a value also accept it as the next argument, e.g. `--dir ../projects`
+                if flag.takes_value() && !flag.has_value() {
+                    if let Some(value) = raw_args.next_if(|next| !next.starts_with("-")) {
+                        flag = Self::map_flag_with_value(format!("{arg}={value}"))?;
+                    }
+                }
+                if flag.requires_value() && !flag.has_value() {
+                    return Err(ProgramError::new(format!(
+                        "'{arg}' needs a value, run again with --help or -h for more info."
+                    )));
+                }
+                flags.push(flag);
+            } else if project_type.is_none() && action == Action::SetUp && arg == "add" {
+                match raw_args.next() {
+                    Some(what) if what == "app" => action = Action::AddApp,
+                    _ => {
+                        return Err(ProgramError::new(
+                            "Expected 'add app <project-type>', run again with --help or -h for more info."
+                                .to_string(),
+                        ))
+                    }
+                }
             } else if project_type.is_none() {
-                project_type = Some(Self::map_string_to_project_type(&arg)?);
+                project_type = Some(Self::map_string_to_project_type(&arg.to_lowercase())?);
             } else {
                 return Err(ProgramError::new(format!(
                     "You can only provide one project type! Found extra type '{arg}'",
@@ -81,11 +219,16 @@ impl ProgramArguments {
         }
 
         Ok(Self {
+            action,
             project_type,
             flags,
         })
     }
 
+    pub fn get_action(&self) -> Action {
+        self.action
+    }
+
     pub fn get_project_type(&self) -> &Option<ProjectType> {
         &self.project_type
     }
@@ -109,7 +252,10 @@ impl ProgramArguments {
     }
 
     fn map_string_to_flag(s: String) -> PEResult<Flag> {
-        let flag = VALID_FLAGS.iter().find(|flag| flag.0 == s || flag.1 == s);
+        let key = s.to_lowercase();
+        let flag = VALID_FLAGS
+            .iter()
+            .find(|flag| flag.0 == key || flag.1 == key);
 
         if let Some(flag) = flag {
             Ok(flag.2.to_owned())
@@ -118,10 +264,11 @@ impl ProgramArguments {
         }
     }
 
+    /// Maps `--key=value`, keeping the case of `value` as it may be a name or path.
     fn map_flag_with_value(s: String) -> PEResult<Flag> {
-        let s_split: Vec<_> = s.split("=").collect();
-        let key = s_split[0];
-        let value = s_split[1];
+        let (key, value) = s.split_once('=').unwrap_or((&s, ""));
+        let key = key.to_lowercase();
+        let key = key.as_str();
 
         let flag = VALID_FLAGS
             .iter()
@@ -129,7 +276,48 @@ impl ProgramArguments {
 
         if let Some(flag) = flag {
             match flag.2 {
-                Flag::Name(_) => Ok(Flag::Name(Value(Some(value.to_string())))),
+                Flag::Name(_)
+                | Flag::Dir(_)
+                | Flag::Variant(_)
+                | Flag::Extras(_)
+                | Flag::DjangoPackage(_)
+                | Flag::Apps(_) => {
+                    Ok(flag.2.with_value(value))
+                }
+                Flag::Timeout(_) | Flag::Retries(_) | Flag::Backoff(_) => {
+                    if value.parse::<u32>().is_ok() {
+                        Ok(flag.2.with_value(value))
+                    } else {
+                        Err(ProgramError::new(format!(
+                            "'{value}' is not a valid value for '{key}', expected a whole number."
+                        )))
+                    }
+                }
+                Flag::Run(_) => Self::map_flag_with_choice(&flag.2, key, value, &VALID_RUN_MODES),
+                Flag::Force(_) => {
+                    Self::map_flag_with_choice(&flag.2, key, value, &VALID_FORCE_MODES)
+                }
+                Flag::PackageManager(_) => {
+                    Self::map_flag_with_choice(&flag.2, key, value, &VALID_PACKAGE_MANAGERS)
+                }
+                Flag::Open(_) => Self::map_flag_with_choice(&flag.2, key, value, &VALID_OPENERS),
+                Flag::Docker(_) => {
+                    Self::map_flag_with_choice(&flag.2, key, value, &VALID_DOCKER_SERVICES)
+                }
+                Flag::Ci(_) => Self::map_flag_with_choice(&flag.2, key, value, &VALID_CI_PROVIDERS),
+                Flag::Lint(_) => {
+                    Self::map_flag_with_choice(&flag.2, key, value, &VALID_LINT_PRESETS)
+                }
+                Flag::Testing(_) => {
+                    Self::map_flag_with_choice(&flag.2, key, value, &VALID_TESTING_MODES)
+                }
+                Flag::License(_) => match license::spdx_id(value) {
+                    Some(id) => Ok(flag.2.with_value(id)),
+                    None => Err(ProgramError::new(format!(
+                        "'{value}' is not a license that can be written with '{key}', expected one of: {}.",
+                        VALID_LICENSES.join(", ")
+                    ))),
+                },
                 _ => Err(ProgramError::new(format!(
                     "'{key}' is not a valid flag, run again with --help or -h for more info."
                 ))),
@@ -140,50 +328,177 @@ impl ProgramArguments {
             )))
         }
     }
+
+    fn map_flag_with_choice(
+        flag: &Flag,
+        key: &str,
+        value: &str,
+        choices: &[&str],
+    ) -> PEResult<Flag> {
+        let value = value.to_lowercase();
+        if choices.contains(&value.as_str()) {
+            Ok(flag.with_value(&value))
+        } else {
+            Err(ProgramError::new(format!(
+                "'{value}' is not a valid value for '{key}', expected one of: {}.",
+                choices.join(", ")
+            )))
+        }
+    }
 }
 
 impl ProgramError {
     pub fn new(message: String) -> Self {
-        Self { message }
+        Self {
+            message,
+            kind: ErrorKind::Failed,
+        }
+    }
+
+    pub fn aborted(message: String) -> Self {
+        Self {
+            message,
+            kind: ErrorKind::Aborted,
+        }
     }
 
     pub fn msg(&self) -> &str {
         &self.message
     }
+
+    pub fn is_aborted(&self) -> bool {
+        self.kind == ErrorKind::Aborted
+    }
 }
 
 impl ProjectType {
-    pub fn set_up(&self, flags: &[Flag]) -> PEResult {
+    pub fn set_up(&self, flags: &[Flag], tool_versions: ToolVersions) -> PEResult {
         Flag::log_if_verbose(format!("setting up {self:?} project").as_str(), flags);
+        SetUp::new(*self, flags)
+            .with_tool_versions(tool_versions)
+            .run()
+    }
 
-        match self {
-            ProjectType::Django => self.set_up_django_project(flags),
-            ProjectType::Web => self.set_up_web_project(flags),
-            ProjectType::Next => self.set_up_next_project(flags),
+    /// Sets the project up as an app of the monorepo the current directory is in, in the monorepo's `apps/` folder.
+    pub fn add_app(&self, flags: &[Flag], tool_versions: ToolVersions) -> PEResult {
+        if !MONOREPO_APP_TYPES.contains(self) {
+            return Err(ProgramError::new(format!(
+                "A {self:?} project can't be added as a monorepo app, expected one of: {}.",
+                MONOREPO_APP_TYPES
+                    .iter()
+                    .map(|project_type| format!("{project_type:?}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
         }
+
+        let current_dir = match env::current_dir() {
+            Ok(current_dir) => current_dir,
+            Err(e) => {
+                return Err(ProgramError::new(format!(
+                    "Failed to read the current directory: {e}"
+                )))
+            }
+        };
+        let Some(root) = pipeline::find_workspace_root(&current_dir) else {
+            return Err(ProgramError::new(format!(
+                "{current_dir:?} is not inside a monorepo (no pnpm-workspace.yaml, or package.json with workspaces, found). Create one with the monorepo project type."
+            )));
+        };
+
+        Flag::log_if_verbose(
+            format!("adding {self:?} app to the monorepo in {root:?}").as_str(),
+            flags,
+        );
+        SetUp::new(*self, flags)
+            .with_tool_versions(tool_versions)
+            .in_dir(root.join("apps"))
+            .run()
     }
 
-    pub fn check_for_required_tooling(&self, flags: &[Flag]) -> PEResult {
+    /// Checks the tools the project type needs are installed, returning the versions found.
+    pub fn check_for_required_tooling(&self, flags: &[Flag]) -> PEResult<ToolVersions> {
         Flag::log_if_verbose(
             format!("checking required tooling for a {self:?} project...").as_str(),
             flags,
         );
 
         match self {
-            ProjectType::Django => self.check_for_django_tooling(),
-            ProjectType::Web => self.check_for_node_js_tooling(),
-            ProjectType::Next => self.check_for_next_tooling(),
+            ProjectType::Django | ProjectType::FastApi | ProjectType::Flask => {
+                self.check_for_python_tooling(flags)
+            }
+            ProjectType::Web
+            | ProjectType::NodeApi
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Electron
+            | ProjectType::Monorepo => self.check_for_node_js_tooling(flags),
+            ProjectType::Tauri => self.check_for_tauri_tooling(flags),
+            ProjectType::Fullstack => Ok(self
+                .check_for_python_tooling(flags)?
+                .merge(self.check_for_node_js_tooling(flags)?)),
+            ProjectType::Next => self.check_for_next_tooling(flags),
+            ProjectType::Rust => self.check_for_rust_tooling(flags),
+            ProjectType::Go => self.check_for_go_tooling(flags),
         }
     }
 
-    fn check_for_django_tooling(&self) -> PEResult {
+    /// Port the project type's dev server listens on by default, `None` if what it runs is not a server.
+    pub fn dev_server_port(&self) -> Option<u16> {
+        match self {
+            ProjectType::Django | ProjectType::FastApi => Some(8000),
+            ProjectType::Flask => Some(5000),
+            ProjectType::Web
+            | ProjectType::SvelteKit
+            | ProjectType::Remix
+            | ProjectType::Fullstack => Some(5173),
+            ProjectType::Next | ProjectType::NodeApi | ProjectType::Nuxt => Some(3000),
+            ProjectType::Astro => Some(4321),
+            ProjectType::Rust
+            | ProjectType::Go
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Monorepo => None,
+        }
+    }
+
+    /// Retry policy for the project type's network-bound steps, before any command line overrides.
+    pub fn default_retry_policy(&self) -> RetryPolicy {
+        match self {
+            ProjectType::Django | ProjectType::FastApi | ProjectType::Flask => {
+                RetryPolicy::new(Some(Duration::from_secs(300)), 2, Duration::from_secs(5))
+            }
+            ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::NodeApi
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Fullstack
+            | ProjectType::Monorepo
+            | ProjectType::Rust
+            | ProjectType::Go => {
+                RetryPolicy::new(Some(Duration::from_secs(600)), 2, Duration::from_secs(5))
+            }
+        }
+    }
+
+    fn check_for_python_tooling(&self, flags: &[Flag]) -> PEResult<ToolVersions> {
+        let mut versions = ToolVersions::default();
+
         // check for python
-        let cmds = ["python --version", "python3 -version"];
-        if utils::check_if_any_command_passes(&cmds).is_err() {
+        let cmds = ["python --version", "python3 --version"];
+        let Some(version) = utils::command_version(&cmds) else {
             return Err(ProgramError::new(format!(
                 "Could not confirm if python is installed, in order to set up a {self:?} project."
             )));
-        }
+        };
+        versions.add("python", &version, flags);
 
         // check for python venv
         let cmds = ["python -m venv --help", "python3 -m venv --help"];
@@ -195,305 +510,704 @@ impl ProjectType {
 
         // check for python pip
         let cmds = ["python -m pip --version", "python3 -m pip --version"];
-        if utils::check_if_any_command_passes(&cmds).is_err() {
+        let Some(version) = utils::command_version(&cmds) else {
             return Err(ProgramError::new(format!(
                 "Could not confirm if the pip package manager is installed, in order to set up a {self:?} project."
             )));
         };
+        versions.add("pip", &version, flags);
 
-        Ok(())
+        Ok(versions)
     }
 
-    fn check_for_node_js_tooling(&self) -> PEResult {
+    fn check_for_node_js_tooling(&self, flags: &[Flag]) -> PEResult<ToolVersions> {
+        let mut versions = ToolVersions::default();
+
         // check for node js
-        let cmds = ["node --version"];
-        if utils::check_if_any_command_passes(&cmds).is_err() {
+        let Some(version) = utils::command_version(&["node --version"]) else {
             return Err(ProgramError::new(format!(
                 "Could not confirm if Node js is installed, in order to set up a {self:?} project."
             )));
-        }
+        };
+        versions.add("node", &version, flags);
 
         // check for npm
-        let cmds = ["npm --version"];
-        if utils::check_if_any_command_passes(&cmds).is_err() {
+        let Some(version) = utils::command_version(&["npm --version"]) else {
             return Err(ProgramError::new(format!(
                 "Could not confirm if Npm is installed, in order to set up a {self:?} project."
             )));
+        };
+        versions.add("npm", &version, flags);
+
+        // check for the package manager picked with --package-manager
+        let package_manager = Flag::get_package_manager(flags);
+        if package_manager != PackageManager::Npm {
+            let cmd = format!("{} --version", package_manager.name());
+            let Some(version) = utils::command_version(&[cmd.as_str()]) else {
+                return Err(ProgramError::new(format!(
+                    "Could not confirm if {} is installed, in order to set up a {self:?} project.",
+                    package_manager.name()
+                )));
+            };
+            versions.add(package_manager.name(), &version, flags);
         }
 
-        Ok(())
+        Ok(versions)
     }
 
-    fn check_for_next_tooling(&self) -> PEResult {
-        self.check_for_node_js_tooling()
+    fn check_for_next_tooling(&self, flags: &[Flag]) -> PEResult<ToolVersions> {
+        self.check_for_node_js_tooling(flags)
     }
 
-    fn set_up_django_project(&self, flags: &[Flag]) -> PEResult {
-        // create dir
-        let flag_set_proj_name = Flag::get_project_name(&flags);
-        let mut proj_name = if let Some(s) = flag_set_proj_name {
-            s
-        } else {
-            prompt_input("Enter project name: ")?
-        };
-
-        proj_name = proj_name.trim().to_string();
-        Flag::log_if_verbose(format!("creating {proj_name:?} directory").as_str(), flags);
+    fn check_for_rust_tooling(&self, flags: &[Flag]) -> PEResult<ToolVersions> {
+        let mut versions = ToolVersions::default();
 
-        let is_test_run = Flag::is_test_run(&flags);
-        if is_test_run {
-            proj_name = format!("test_runs/{proj_name}");
-            let test_run_path = Path::new("test_runs");
-            if !test_run_path.try_exists().is_ok_and(|b| b) {
-                if let Err(e) = fs::DirBuilder::new().create(test_run_path) {
+        // check for cargo and rustc
+        for tool in ["cargo", "rustc"] {
+            match utils::command_version(&[&format!("{tool} --version")]) {
+                Some(version) => versions.add(tool, &version, flags),
+                None => {
                     return Err(ProgramError::new(format!(
-                        "Failed to create test_runs directory '{}'. ",
-                        e.kind()
-                    )));
+                        "Could not confirm if {tool} is installed, in order to set up a {self:?} project. See https://rustup.rs"
+                    )))
                 }
             }
         }
 
-        if let Err(e) = fs::DirBuilder::new().create(&proj_name) {
+        Ok(versions)
+    }
+
+    fn check_for_tauri_tooling(&self, flags: &[Flag]) -> PEResult<ToolVersions> {
+        let versions = self
+            .check_for_node_js_tooling(flags)?
+            .merge(self.check_for_rust_tooling(flags)?);
+
+        // check for the system libraries tauri builds against on linux (see https://tauri.app/start/prerequisites)
+        if cfg!(target_os = "linux") {
+            if utils::check_if_any_command_passes(&["pkg-config --version"]).is_err() {
+                return Err(ProgramError::new(format!(
+                    "Could not confirm if pkg-config is installed, in order to check the system libraries a {self:?} project needs."
+                )));
+            }
+
+            let missing: Vec<_> = TAURI_LINUX_PKG_CONFIG_PACKAGES
+                .iter()
+                .filter(|package| {
+                    utils::run_seperate_cmd(&format!("pkg-config --exists {package}")).is_err()
+                })
+                .copied()
+                .collect();
+            if !missing.is_empty() {
+                return Err(ProgramError::new(format!(
+                    "Missing system libraries needed to build a {self:?} project, pkg-config could not find: {}. See https://tauri.app/start/prerequisites/#linux for the packages to install.",
+                    missing.join(", ")
+                )));
+            }
+        }
+
+        Ok(versions)
+    }
+
+    fn check_for_go_tooling(&self, flags: &[Flag]) -> PEResult<ToolVersions> {
+        // check for go, `go version` prints e.g. "go version go1.22.1 linux/amd64"
+        let version = utils::command_version(&["go version"]);
+        let Some(version) = version.filter(|version| version.starts_with("go version go")) else {
             return Err(ProgramError::new(format!(
-                "Failed to create project folder '{}'. ",
-                e.kind()
+                "Could not confirm if go is installed, in order to set up a {self:?} project. See https://go.dev/doc/install"
             )));
-        }
+        };
+        let mut versions = ToolVersions::default();
+        versions.add("go", &version, flags);
 
-        // create terminal
-        let proj_dir = env::current_dir().unwrap().join(&proj_name);
-        let mut terminal = Terminal::new(proj_dir);
+        Ok(versions)
+    }
 
-        // setup venv
-        terminal.run_cmd(
-            "python -m venv env",
-            "Failed to create virtual env.",
-            "setting up virtual environment",
-            flags,
-        )?;
+    pub fn naming_rules(&self) -> NamingRules {
+        match self {
+            ProjectType::Django | ProjectType::FastApi | ProjectType::Flask => {
+                NamingRules::PythonIdentifier
+            }
+            ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::NodeApi
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Fullstack
+            | ProjectType::Monorepo => NamingRules::NpmPackage,
+            ProjectType::Rust => NamingRules::Crate,
+            ProjectType::Go => NamingRules::GoModule,
+        }
+    }
+}
 
-        let activate_cmd = if cfg!(windows) {
-            "env\\Scripts\\activate.bat"
+impl Terminal {
+    pub fn new(working_dir: PathBuf) -> Self {
+        if cfg!(windows) {
+            Self {
+                base_shell_args: [String::from("cmd"), String::from("/C")],
+                working_dir,
+            }
         } else {
-            "source env/bin/activate"
+            Self {
+                base_shell_args: [String::from("sh"), String::from("-c")],
+                working_dir,
+            }
+        }
+    }
+
+    pub fn run_cmd(&mut self, cmd: &str, err_msg: &str, log_msg: &str, flags: &[Flag]) -> PEResult {
+        Flag::log_if_verbose(log_msg, flags);
+        let mut command = Command::new(&self.base_shell_args[0]);
+        command
+            .arg(&self.base_shell_args[1])
+            .arg(cmd)
+            .current_dir(&self.working_dir)
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit());
+
+        // Children that can prompt on a terminal stay in our (foreground) process group, so Ctrl-C reaches them directly.
+        // Otherwise they lead their own group, which signals are forwarded to as a whole.
+        let own_group = cfg!(unix) && !io::stdin().is_terminal();
+        if own_group {
+            utils::new_process_group(&mut command);
+        }
+
+        signals::check_for_abort()?;
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(e) => return Err(ProgramError::new(format!("{err_msg} {e}"))),
         };
 
-        // install django
-        terminal.run_cmd(
-            &format!("{activate_cmd} && pip install django"),
-            "Failed to install django with pip.",
-            "installing django",
-            flags,
-        )?;
+        signals::set_running_child(child.id(), own_group);
+        let status = child.wait();
+        signals::clear_running_child();
 
-        // start a django project
-        terminal.run_cmd(
-            &format!("{activate_cmd} && django-admin startproject core ."),
-            "Failed to start a django project.",
-            "starting a django project",
-            flags,
-        )?;
+        if let Some(signal) = signals::take_received_signal() {
+            return Err(ProgramError::aborted(format!(
+                "{err_msg} Interrupted by signal {signal}."
+            )));
+        }
 
-        // run the dev server
-        terminal.run_cmd(
-            &format!("{activate_cmd} && python manage.py runserver"),
-            "Failed to run dev server.",
-            "running dev server...",
-            flags,
-        )?;
+        match status {
+            Err(e) => Err(ProgramError::new(format!("{err_msg} {e}"))),
+            Ok(status) if !status.success() => Err(ProgramError::new(err_msg.to_string())),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    /// Runs a non-interactive step under `policy`. Unlike `run_cmd`, stdin is closed and the step's stderr is captured
+    /// (as well as shown), so the error after the final failed attempt can include it.
+    pub fn run_cmd_with_policy(
+        &mut self,
+        cmd: &str,
+        err_msg: &str,
+        log_msg: &str,
+        policy: &RetryPolicy,
+        flags: &[Flag],
+    ) -> PEResult {
+        Flag::log_if_verbose(log_msg, flags);
 
-        // TODO open it in file explorer/code
+        let attempts = policy.retries + 1;
+        let mut last_failure = (String::new(), String::new());
+        for attempt in 1..=attempts {
+            if attempt > 1 {
+                let backoff = policy.backoff_before(attempt);
+                yellow_log(
+                    format!(
+                        "retrying `{cmd}` in {}s (attempt {attempt}/{attempts})",
+                        backoff.as_secs()
+                    )
+                    .as_str(),
+                );
+                // slept in slices, so an abort doesn't wait out the whole backoff
+                let resume = Instant::now() + backoff;
+                while Instant::now() < resume {
+                    if let Err(e) = signals::check_for_abort() {
+                        return Err(ProgramError::aborted(format!("{err_msg} {}", e.msg())));
+                    }
+                    thread::sleep(
+                        resume
+                            .saturating_duration_since(Instant::now())
+                            .min(Duration::from_millis(100)),
+                    );
+                }
+            }
 
-        Ok(())
+            let (result, stderr) = self.run_attempt(cmd, policy.timeout);
+            match result {
+                Ok(_) => return Ok(()),
+                Err(e) if e.is_aborted() => {
+                    return Err(ProgramError::aborted(format!("{err_msg} {}", e.msg())))
+                }
+                Err(e) => {
+                    yellow_log(
+                        format!(
+                            "attempt {attempt}/{attempts} of `{cmd}` failed: {}",
+                            e.msg()
+                        )
+                        .as_str(),
+                    );
+                    last_failure = (e.msg().to_string(), stderr);
+                }
+            }
+        }
+
+        let (reason, stderr) = last_failure;
+        Err(ProgramError::new(format!(
+            "{err_msg} Gave up after {attempts} attempt(s), the last one {reason}\nLast stderr output:\n{}",
+            if stderr.trim().is_empty() { "(none)" } else { stderr.trim_end() }
+        )))
     }
 
-    fn set_up_web_project(&self, flags: &[Flag]) -> PEResult {
-        // create dir
-        let flag_set_proj_name = Flag::get_project_name(&flags);
-        let mut proj_name = if let Some(s) = flag_set_proj_name {
-            s
-        } else {
-            prompt_input("Enter project name: ")?
+    /// Runs `cmd` once in its own process group, killing the whole group if it outlives `timeout`.
+    /// Returns the result along with the tail of the command's stderr.
+    fn run_attempt(&self, cmd: &str, timeout: Option<Duration>) -> (PEResult, String) {
+        let mut command = Command::new(&self.base_shell_args[0]);
+        command
+            .arg(&self.base_shell_args[1])
+            .arg(cmd)
+            .current_dir(&self.working_dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::piped());
+        utils::new_process_group(&mut command);
+
+        if let Err(e) = signals::check_for_abort() {
+            return (Err(e), String::new());
+        }
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                return (
+                    Err(ProgramError::new(format!("could not start: {e}"))),
+                    String::new(),
+                )
+            }
         };
 
-        proj_name = proj_name.trim().to_string();
-        Flag::log_if_verbose(format!("creating {proj_name:?} directory").as_str(), flags);
+        signals::set_running_child(child.id(), cfg!(unix));
+        let stderr_tail = child.stderr.take().map(utils::tee_stderr_tail);
+
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break Ok(status),
+                Ok(None) if deadline.is_some_and(|deadline| Instant::now() >= deadline) => {
+                    utils::kill_process_group(&mut child);
+                    break Err(format!(
+                        "timed out after {}s",
+                        timeout.unwrap_or_default().as_secs()
+                    ));
+                }
+                Ok(None) => thread::sleep(Duration::from_millis(100)),
+                Err(e) => break Err(format!("could not be waited on: {e}")),
+            }
+        };
+        signals::clear_running_child();
 
-        let is_test_run = Flag::is_test_run(&flags);
-        if is_test_run {
-            proj_name = format!("test_runs/{proj_name}");
-            let test_run_path = Path::new("test_runs");
-            if !test_run_path.try_exists().is_ok_and(|b| b) {
-                if let Err(e) = fs::DirBuilder::new().create(test_run_path) {
-                    return Err(ProgramError::new(format!(
-                        "Failed to create test_runs directory '{}'. ",
-                        e.kind()
-                    )));
+        let stderr = stderr_tail
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default();
+
+        if let Some(signal) = signals::take_received_signal() {
+            return (
+                Err(ProgramError::aborted(format!(
+                    "Interrupted by signal {signal}."
+                ))),
+                stderr,
+            );
+        }
+
+        let result = match status {
+            Ok(status) if status.success() => Ok(()),
+            Ok(status) => Err(ProgramError::new(format!("exited with {status}"))),
+            Err(reason) => Err(ProgramError::new(reason)),
+        };
+        (result, stderr)
+    }
+
+    pub fn run_dev_server(
+        &mut self,
+        cmd: &str,
+        port: Option<u16>,
+        err_msg: &str,
+        flags: &[Flag],
+    ) -> PEResult {
+        // the project is fully set up by now, so there is nothing partial to clean up if the user aborts
+        signals::track_partial_project(None);
+
+        match Flag::get_run_mode(flags) {
+            RunMode::Foreground => {
+                match self.run_cmd(cmd, err_msg, "running dev server...", flags) {
+                    // Ctrl-C is how a foreground dev server is meant to be stopped
+                    Err(e) if e.is_aborted() => {
+                        yellow_log("dev server stopped");
+                        Ok(())
+                    }
+                    result => result,
                 }
             }
+            RunMode::Background => self.run_detached_dev_server(cmd, port, err_msg, flags),
+            RunMode::Skip => {
+                yellow_log(
+                    format!(
+                        "skipped the dev server, start it with `{cmd}` from {:#?}",
+                        self.working_dir
+                    )
+                    .as_str(),
+                );
+                Ok(())
+            }
         }
+    }
 
-        if let Err(e) = fs::DirBuilder::new().create(&proj_name) {
+    /// Starts the dev server detached from this process, then waits for `port` to answer, if it's a server at all.
+    /// The server's output goes to a log file in the OS temp directory, as nothing will be reading our terminal once we exit.
+    fn run_detached_dev_server(
+        &mut self,
+        cmd: &str,
+        port: Option<u16>,
+        err_msg: &str,
+        flags: &[Flag],
+    ) -> PEResult {
+        Flag::log_if_verbose("starting dev server in the background...", flags);
+
+        // the dev server would move to another port, and whatever answers on this one be reported as it
+        if let Some(port) = port.filter(|port| utils::port_answers(*port)) {
             return Err(ProgramError::new(format!(
-                "Failed to create project folder '{}'. ",
-                e.kind()
+                "{err_msg} Port {port} is already in use, stop what is listening on it and run the dev server by hand."
             )));
         }
 
-        // create terminal
-        let proj_dir = env::current_dir().unwrap().join(&proj_name);
-        let mut terminal = Terminal::new(proj_dir.clone());
+        let log_name = match port {
+            Some(port) => format!("plateboiler-dev-server-{port}.log"),
+            None => format!("plateboiler-run-{}.log", std::process::id()),
+        };
+        let log_path = env::temp_dir().join(log_name);
+        let log_file = fs::File::create(&log_path).and_then(|file| Ok((file.try_clone()?, file)));
+        let (stdout_log, stderr_log) = match log_file {
+            Ok(files) => files,
+            Err(e) => {
+                return Err(ProgramError::new(format!(
+                    "{err_msg} Could not create dev server log file {log_path:?}: {e}"
+                )))
+            }
+        };
 
-        // run vite cli
-        terminal.run_cmd(
-            "npm create vite@latest",
-            "Failed to create vite app with npm.",
-            "creating vite app",
-            flags,
-        )?;
-
-        // cd into project
-        let proj_dir_contents = proj_dir.read_dir();
-        if let Ok(mut dirs) = proj_dir_contents {
-            if let Some(dir) = dirs.next() {
-                if dir.is_ok() {
-                    terminal.working_dir = dir.unwrap().path();
-                    green_log(format!("moved into: {:#?}", terminal.working_dir).as_str());
-                };
-            };
+        let mut command = Command::new(&self.base_shell_args[0]);
+        command
+            .arg(&self.base_shell_args[1])
+            .arg(cmd)
+            .current_dir(&self.working_dir)
+            .stdin(Stdio::null())
+            .stdout(stdout_log)
+            .stderr(stderr_log);
+        utils::detach(&mut command);
+
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(e) => return Err(ProgramError::new(format!("{err_msg} {e}"))),
         };
 
-        // npm install
-        terminal.run_cmd(
-            "npm install",
-            "Failed to install node modules.",
-            "installing node modules...",
-            flags,
-        )?;
+        let Some(port) = port else {
+            green_log(
+                format!(
+                    "running in the background (pid {}, output in {log_path:?})",
+                    child.id()
+                )
+                .as_str(),
+            );
+            return Ok(());
+        };
 
-        // run the dev server
-        terminal.run_cmd(
-            "npm run dev",
-            "Failed to run dev server.",
-            "running dev server...",
-            flags,
-        )?;
+        let deadline = Instant::now() + Duration::from_secs(DEV_SERVER_STARTUP_TIMEOUT_SECS);
+        while Instant::now() < deadline {
+            if let Ok(Some(status)) = child.try_wait() {
+                return Err(ProgramError::new(format!(
+                    "{err_msg} The dev server exited ({status}) before answering on port {port}, see {log_path:?}."
+                )));
+            }
+            if let Some(signal) = signals::take_received_signal() {
+                return Err(ProgramError::aborted(format!(
+                    "Interrupted by signal {signal}. The dev server is still starting with pid {}, see {log_path:?}.",
+                    child.id()
+                )));
+            }
 
-        // TODO open it in file explorer/code
+            if utils::port_answers(port) {
+                green_log(
+                    format!(
+                        "dev server running in the background at http://localhost:{port}/ (pid {}, logs in {log_path:?})",
+                        child.id()
+                    )
+                    .as_str(),
+                );
+                return Ok(());
+            }
+
+            thread::sleep(Duration::from_millis(250));
+        }
 
-        Ok(())
+        Err(ProgramError::new(format!(
+            "{err_msg} The dev server did not answer on port {port} within {DEV_SERVER_STARTUP_TIMEOUT_SECS} seconds. It is still running with pid {}, see {log_path:?}.",
+            child.id()
+        )))
     }
+}
 
-    fn set_up_next_project(&self, flags: &[Flag]) -> PEResult {
-        // create dir
-        let flag_set_proj_name = Flag::get_project_name(&flags);
-        let mut proj_name = if let Some(s) = flag_set_proj_name {
-            s
-        } else {
-            prompt_input("Enter project name: ")?
+impl PackageManager {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Bun => "bun",
+        }
+    }
+
+    /// Command running `package` (e.g. `create-vite@latest`) without installing it, the way `npx` does.
+    pub fn exec_cmd(&self, package: &str, args: &str) -> String {
+        let exec = match self {
+            PackageManager::Npm => "npx",
+            PackageManager::Pnpm => "pnpm dlx",
+            PackageManager::Yarn => "yarn dlx",
+            PackageManager::Bun => "bunx",
+        };
+        format!("{exec} {package} {args}").trim_end().to_string()
+    }
+
+    /// Command making the package manager available where only node is installed, as in a container.
+    pub fn setup_cmd(&self) -> Option<&'static str> {
+        match self {
+            PackageManager::Npm => None,
+            PackageManager::Pnpm | PackageManager::Yarn => Some("corepack enable"),
+            PackageManager::Bun => Some("npm install --global bun"),
+        }
+    }
+
+    /// Command running `bin` from the project's own dependencies, unlike `exec_cmd`.
+    pub fn bin_cmd(&self, bin: &str, args: &str) -> String {
+        let exec = match self {
+            PackageManager::Npm => "npx",
+            PackageManager::Pnpm => "pnpm exec",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Bun => "bunx",
         };
+        format!("{exec} {bin} {args}").trim_end().to_string()
+    }
 
-        proj_name = proj_name.trim().to_string();
-        Flag::log_if_verbose(format!("creating {proj_name:?} directory").as_str(), flags);
+    pub fn install_cmd(&self) -> String {
+        format!("{} install", self.name())
+    }
 
-        let is_test_run = Flag::is_test_run(&flags);
-        if is_test_run {
-            proj_name = format!("test_runs/{proj_name}");
-            let test_run_path = Path::new("test_runs");
-            if !test_run_path.try_exists().is_ok_and(|b| b) {
-                if let Err(e) = fs::DirBuilder::new().create(test_run_path) {
-                    return Err(ProgramError::new(format!(
-                        "Failed to create test_runs directory '{}'. ",
-                        e.kind()
-                    )));
-                }
-            }
+    /// Command adding `packages` to the project's dependencies, or its dev dependencies if `dev`.
+    pub fn add_cmd(&self, packages: &str, dev: bool) -> String {
+        match (self, dev) {
+            (PackageManager::Npm, false) => format!("npm install {packages}"),
+            (PackageManager::Npm, true) => format!("npm install --save-dev {packages}"),
+            (_, false) => format!("{} add {packages}", self.name()),
+            (_, true) => format!("{} add -D {packages}", self.name()),
         }
+    }
 
-        if let Err(e) = fs::DirBuilder::new().create(&proj_name) {
-            return Err(ProgramError::new(format!(
-                "Failed to create project folder '{}'. ",
-                e.kind()
-            )));
+    pub fn run_script_cmd(&self, script: &str) -> String {
+        format!("{} run {script}", self.name())
+    }
+
+    /// Command adding `packages` to the dev dependencies of a workspace root, which pnpm has to be told about.
+    pub fn add_to_workspace_root_cmd(&self, packages: &str) -> String {
+        match self {
+            PackageManager::Pnpm => format!("pnpm add -D -w {packages}"),
+            _ => self.add_cmd(packages, true),
         }
+    }
 
-        // create terminal
-        let proj_dir = env::current_dir().unwrap().join(&proj_name);
-        let mut terminal = Terminal::new(proj_dir.clone());
+    /// Command running `script` in every package of a workspace that has it.
+    pub fn workspaces_run_cmd(&self, script: &str) -> String {
+        match self {
+            PackageManager::Npm => format!("npm run {script} --workspaces --if-present"),
+            PackageManager::Pnpm => format!("pnpm -r run {script}"),
+            PackageManager::Yarn => format!("yarn workspaces foreach -A run {script}"),
+            PackageManager::Bun => format!("bun run --filter '*' {script}"),
+        }
+    }
+}
 
-        // run next cli
-        terminal.run_cmd(
-            "npx create-next-app@latest",
-            "Failed to create next app with npm.",
-            "creating next app",
-            flags,
-        )?;
-
-        // cd into project
-        let proj_dir_contents = proj_dir.read_dir();
-        if let Ok(mut dirs) = proj_dir_contents {
-            if let Some(dir) = dirs.next() {
-                if dir.is_ok() {
-                    terminal.working_dir = dir.unwrap().path();
-                    green_log(format!("moved into: {:#?}", terminal.working_dir).as_str());
-                };
-            };
-        };
+impl ToolVersions {
+    pub fn get(&self, tool: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(name, _)| *name == tool)
+            .map(|(_, version)| version.as_str())
+    }
 
-        // run the dev server
-        terminal.run_cmd(
-            "npm run dev",
-            "Failed to run dev server.",
-            "running dev server...",
-            flags,
-        )?;
+    /// The version of `tool` cut to its first `parts` numbers, e.g. "3.12" for python 3.12.1, to pin an image to.
+    pub fn pinned(&self, tool: &str, parts: usize) -> Option<String> {
+        self.get(tool)
+            .map(|version| version.split('.').take(parts).collect::<Vec<_>>().join("."))
+    }
+
+    /// `pinned`, or `default` if `tool` wasn't found.
+    pub fn pinned_or(&self, tool: &str, parts: usize, default: &str) -> String {
+        self.pinned(tool, parts)
+            .unwrap_or_else(|| default.to_string())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.0
+            .iter()
+            .map(|(tool, version)| (*tool, version.as_str()))
+    }
 
-        // TODO open it in file explorer/code
+    /// Records the version of `tool` from the line its version command printed, e.g. "Python 3.12.1".
+    fn add(&mut self, tool: &'static str, version_line: &str, flags: &[Flag]) {
+        Flag::log_if_verbose(format!("found {version_line}").as_str(), flags);
+        if let Some(version) = utils::parse_version(version_line) {
+            self.0.push((tool, version));
+        }
+    }
 
-        Ok(())
+    fn merge(mut self, other: Self) -> Self {
+        for (tool, version) in other.0 {
+            if self.get(tool).is_none() {
+                self.0.push((tool, version));
+            }
+        }
+        self
     }
 }
 
-impl Terminal {
-    pub fn new(working_dir: PathBuf) -> Self {
-        if cfg!(windows) {
-            Self {
-                base_shell_args: [String::from("cmd"), String::from("/C")],
-                working_dir,
+impl Opener {
+    /// The opener called `name`, one of `VALID_OPENERS`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "editor" => Some(Opener::Editor),
+            "code" => Some(Opener::Code),
+            "idea" => Some(Opener::Idea),
+            "files" => Some(Opener::FileManager),
+            "none" => Some(Opener::Off),
+            _ => None,
+        }
+    }
+
+    /// This opener, with `Detect` resolved to the first available one, and the program (and its arguments) it runs,
+    /// if it is available.
+    fn resolve(self) -> Option<(Self, Vec<String>)> {
+        let available =
+            |program: &str| utils::command_exists(program).then(|| vec![program.to_string()]);
+        let program = match self {
+            Opener::Detect => {
+                return [
+                    Opener::Editor,
+                    Opener::Code,
+                    Opener::Idea,
+                    Opener::FileManager,
+                ]
+                .into_iter()
+                .find_map(Opener::resolve)
             }
+            // may be set with arguments, e.g. `code --wait`
+            Opener::Editor => ["VISUAL", "EDITOR"].iter().find_map(|var| {
+                let editor = env::var(var).ok()?;
+                let program: Vec<String> = editor.split_whitespace().map(String::from).collect();
+                utils::command_exists(program.first()?).then_some(program)
+            }),
+            Opener::Code => available("code"),
+            Opener::Idea => available("idea"),
+            Opener::FileManager if cfg!(windows) => Some(vec![String::from("explorer")]),
+            Opener::FileManager => available("xdg-open"),
+            Opener::Off => None,
+        };
+        program.map(|program| (self, program))
+    }
+
+    /// Opens `dir`. Editors from `$VISUAL`/`$EDITOR` may be terminal editors, so they are run in the foreground, the
+    /// rest are left running detached from this process.
+    pub fn open(self, dir: &Path) -> PEResult {
+        if self == Opener::Off {
+            return Ok(());
+        }
+        let Some((opener, program)) = self.resolve() else {
+            return Err(ProgramError::new(match self {
+                Opener::Detect => String::from(
+                    "Found nothing to open the project with, set $VISUAL or $EDITOR, or install code, idea or xdg-open.",
+                ),
+                opener => format!("{opener:?} is not available to open the project with."),
+            }));
+        };
+
+        // code and idea are batch scripts on windows, which only cmd knows how to run
+        let mut command = if cfg!(windows) {
+            let mut command = Command::new("cmd");
+            command.arg("/C").args(&program);
+            command
         } else {
-            Self {
-                base_shell_args: [String::from("sh"), String::from("-c")],
-                working_dir,
+            let mut command = Command::new(&program[0]);
+            command.args(&program[1..]);
+            command
+        };
+        command.arg(dir);
+
+        let result = if opener == Opener::Editor {
+            command.status().map(|_| ())
+        } else {
+            command
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null());
+            utils::detach(&mut command);
+            command.spawn().map(|_| ())
+        };
+
+        match result {
+            Ok(_) => {
+                green_log(format!("opened the project with {}", program[0]).as_str());
+                Ok(())
             }
+            Err(e) => Err(ProgramError::new(format!(
+                "Failed to open the project with {}: {e}",
+                program[0]
+            ))),
         }
     }
+}
 
-    pub fn run_cmd(&mut self, cmd: &str, err_msg: &str, log_msg: &str, flags: &[Flag]) -> PEResult {
-        Flag::log_if_verbose(log_msg, flags);
-        let output = Command::new(&self.base_shell_args[0])
-            .arg(&self.base_shell_args[1])
-            .arg(cmd)
-            .current_dir(&self.working_dir)
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .output();
+impl RetryPolicy {
+    pub fn new(timeout: Option<Duration>, retries: u32, backoff: Duration) -> Self {
+        Self {
+            timeout,
+            retries,
+            backoff,
+        }
+    }
 
-        if let Err(e) = output {
-            return Err(ProgramError::new(format!("{err_msg} {e}")));
-        } else {
-            let output = output.unwrap();
-            let output_text = String::from_utf8_lossy(&output.stdout);
-            yellow_log(&output_text);
+    /// The project type's default policy, with `--timeout`, `--retries` and `--backoff` applied over it.
+    pub fn build(project_type: &ProjectType, flags: &[Flag]) -> Self {
+        let mut policy = project_type.default_retry_policy();
 
-            if !output.status.success() {
-                return Err(ProgramError::new(err_msg.to_string()));
-            }
+        if let Some(secs) = Flag::get_number(flags, |flag| matches!(flag, Flag::Timeout(_))) {
+            // a timeout of 0 turns timing out off
+            policy.timeout = (secs > 0).then(|| Duration::from_secs(secs.into()));
+        }
+        if let Some(retries) = Flag::get_number(flags, |flag| matches!(flag, Flag::Retries(_))) {
+            policy.retries = retries;
         }
+        if let Some(secs) = Flag::get_number(flags, |flag| matches!(flag, Flag::Backoff(_))) {
+            policy.backoff = Duration::from_secs(secs.into());
+        }
+
+        policy
+    }
 
-        Ok(())
+    /// How long to wait before `attempt` (2 or more), doubling after each failed attempt.
+    fn backoff_before(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(2)))
     }
 }
 
@@ -504,11 +1218,8 @@ impl Flag {
         }
     }
 
-    fn get_project_name(flags: &[Self]) -> Option<String> {
-        let name = flags.iter().find(|flag| match flag {
-            Self::Name(_) => true,
-            _ => false,
-        });
+    pub fn get_project_name(flags: &[Self]) -> Option<String> {
+        let name = flags.iter().find(|flag| matches!(flag, Self::Name(_)));
 
         if let Some(Self::Name(Value(Some(name)))) = name {
             Some(name.to_string())
@@ -517,10 +1228,286 @@ impl Flag {
         }
     }
 
-    fn is_test_run(flags: &[Self]) -> bool {
+    pub fn is_test_run(flags: &[Self]) -> bool {
         flags.contains(&Self::Test)
     }
 
+    /// Copy of a value-carrying flag with `value` set.
+    fn with_value(&self, value: &str) -> Self {
+        let value = Value(Some(value.to_string()));
+        match self {
+            Self::Name(_) => Self::Name(value),
+            Self::Run(_) => Self::Run(value),
+            Self::Timeout(_) => Self::Timeout(value),
+            Self::Retries(_) => Self::Retries(value),
+            Self::Backoff(_) => Self::Backoff(value),
+            Self::Dir(_) => Self::Dir(value),
+            Self::Force(_) => Self::Force(value),
+            Self::Variant(_) => Self::Variant(value),
+            Self::Extras(_) => Self::Extras(value),
+            Self::PackageManager(_) => Self::PackageManager(value),
+            Self::Open(_) => Self::Open(value),
+            Self::License(_) => Self::License(value),
+            Self::Docker(_) => Self::Docker(value),
+            Self::Ci(_) => Self::Ci(value),
+            Self::Lint(_) => Self::Lint(value),
+            Self::Testing(_) => Self::Testing(value),
+            Self::DjangoPackage(_) => Self::DjangoPackage(value),
+            Self::Apps(_) => Self::Apps(value),
+            _ => self.clone(),
+        }
+    }
+
+    /// Whether the flag carries a value, which may also be given as the next argument.
+    fn takes_value(&self) -> bool {
+        matches!(self, Self::Name(_)) || self.requires_value()
+    }
+
+    /// Whether the flag is meaningless without a value.
+    fn requires_value(&self) -> bool {
+        matches!(
+            self,
+            Self::Timeout(_)
+                | Self::Retries(_)
+                | Self::Backoff(_)
+                | Self::Dir(_)
+                | Self::Variant(_)
+                | Self::Extras(_)
+                | Self::PackageManager(_)
+                | Self::License(_)
+                | Self::Ci(_)
+                | Self::Apps(_)
+        )
+    }
+
+    fn has_value(&self) -> bool {
+        matches!(
+            self,
+            Self::Name(Value(Some(_)))
+                | Self::Run(Value(Some(_)))
+                | Self::Timeout(Value(Some(_)))
+                | Self::Retries(Value(Some(_)))
+                | Self::Backoff(Value(Some(_)))
+                | Self::Dir(Value(Some(_)))
+                | Self::Force(Value(Some(_)))
+                | Self::Variant(Value(Some(_)))
+                | Self::Extras(Value(Some(_)))
+                | Self::PackageManager(Value(Some(_)))
+                | Self::Open(Value(Some(_)))
+                | Self::License(Value(Some(_)))
+                | Self::Docker(Value(Some(_)))
+                | Self::Ci(Value(Some(_)))
+                | Self::Lint(Value(Some(_)))
+                | Self::Testing(Value(Some(_)))
+                | Self::DjangoPackage(Value(Some(_)))
+                | Self::Apps(Value(Some(_)))
+        )
+    }
+
+    pub fn get_target_dir(flags: &[Self]) -> Option<String> {
+        flags.iter().find_map(|flag| match flag {
+            Self::Dir(Value(Some(dir))) => Some(dir.to_string()),
+            _ => None,
+        })
+    }
+
+    pub fn get_variant(flags: &[Self]) -> Option<String> {
+        flags.iter().find_map(|flag| match flag {
+            Self::Variant(Value(Some(variant))) => Some(variant.to_lowercase()),
+            _ => None,
+        })
+    }
+
+    /// The comma separated `--extras` list, `Some(vec![])` for `--extras=none`.
+    pub fn get_extras(flags: &[Self]) -> Option<Vec<String>> {
+        flags.iter().find_map(|flag| match flag {
+            Self::Extras(Value(Some(extras))) => Some(
+                extras
+                    .split(',')
+                    .map(|extra| extra.trim().to_lowercase())
+                    .filter(|extra| !extra.is_empty() && extra != "none")
+                    .collect(),
+            ),
+            _ => None,
+        })
+    }
+
+    pub fn get_force_mode(flags: &[Self]) -> ForceMode {
+        let force = flags.iter().find(|flag| matches!(flag, Self::Force(_)));
+        match force {
+            Some(Self::Force(Value(Some(mode)))) if mode == "overwrite" => ForceMode::Overwrite,
+            Some(_) => ForceMode::AllowEmpty,
+            None => ForceMode::Off,
+        }
+    }
+
+    /// `flags`, with `--package-manager` set to the one of the monorepo the current directory is in if it wasn't passed,
+    /// for apps added to it with `add app`.
+    pub fn with_workspace_package_manager(flags: &[Self]) -> Vec<Self> {
+        let mut flags = flags.to_vec();
+        if flags
+            .iter()
+            .any(|flag| matches!(flag, Self::PackageManager(_)))
+        {
+            return flags;
+        }
+
+        let name = env::current_dir()
+            .ok()
+            .and_then(|dir| pipeline::find_workspace_root(&dir))
+            .and_then(|root| pipeline::workspace_package_manager(&root));
+        if let Some(name) = name {
+            Flag::log_if_verbose(
+                format!("using the monorepo's package manager, {name}").as_str(),
+                &flags,
+            );
+            flags.push(Self::PackageManager(Value(Some(name.to_string()))));
+        }
+        flags
+    }
+
+    pub fn get_package_manager(flags: &[Self]) -> PackageManager {
+        let package_manager = flags.iter().find_map(|flag| match flag {
+            Self::PackageManager(Value(Some(name))) => Some(name.as_str()),
+            _ => None,
+        });
+        match package_manager {
+            Some("pnpm") => PackageManager::Pnpm,
+            Some("yarn") => PackageManager::Yarn,
+            Some("bun") => PackageManager::Bun,
+            _ => PackageManager::Npm,
+        }
+    }
+
+    /// What `--open` asks the project to be opened with, `None` if it wasn't passed.
+    pub fn get_opener(flags: &[Self]) -> Option<Opener> {
+        flags.iter().find_map(|flag| match flag {
+            Self::Open(Value(Some(name))) => Opener::from_name(name),
+            Self::Open(Value(None)) => Some(Opener::Detect),
+            _ => None,
+        })
+    }
+
+    /// SPDX id of the license asked for with `--license`.
+    pub fn get_license(flags: &[Self]) -> Option<&str> {
+        flags.iter().find_map(|flag| match flag {
+            Self::License(Value(Some(id))) => Some(id.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn wants_docker(flags: &[Self]) -> bool {
+        flags.iter().any(|flag| matches!(flag, Self::Docker(_)))
+    }
+
+    /// Whether a Postgres service should be added to the compose.yaml, see the `--docker` flag.
+    pub fn wants_postgres(flags: &[Self]) -> bool {
+        flags
+            .iter()
+            .any(|flag| matches!(flag, Self::Docker(Value(Some(service))) if service == "postgres"))
+    }
+
+    /// Where the CI pipeline written for the project runs, one of `VALID_CI_PROVIDERS`, see the `--ci` flag.
+    pub fn get_ci_provider(flags: &[Self]) -> Option<&str> {
+        flags.iter().find_map(|flag| match flag {
+            Self::Ci(Value(Some(provider))) => Some(provider.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn wants_lint(flags: &[Self]) -> bool {
+        flags.iter().any(|flag| matches!(flag, Self::Lint(_)))
+    }
+
+    /// The lint preset asked for with `--lint=<preset>`, `None` if it was passed without one.
+    pub fn get_lint_preset(flags: &[Self]) -> Option<&str> {
+        flags.iter().find_map(|flag| match flag {
+            Self::Lint(Value(Some(preset))) => Some(preset.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn wants_testing(flags: &[Self]) -> bool {
+        flags.iter().any(|flag| matches!(flag, Self::Testing(_)))
+    }
+
+    /// Whether the test suite should be run once it's set up, see the `--testing` flag.
+    pub fn runs_tests(flags: &[Self]) -> bool {
+        flags
+            .iter()
+            .any(|flag| matches!(flag, Self::Testing(Value(Some(mode))) if mode == "run"))
+    }
+
+    /// The package asked for with `--django-package=<package>`, `Some(None)` if it was passed without one, to name the
+    /// package after the project.
+    pub fn get_django_package(flags: &[Self]) -> Option<Option<&str>> {
+        flags.iter().find_map(|flag| match flag {
+            Self::DjangoPackage(Value(value)) => {
+                Some(value.as_deref().filter(|value| !value.is_empty()))
+            }
+            _ => None,
+        })
+    }
+
+    /// The comma separated `--apps` list, empty if it wasn't passed.
+    pub fn get_apps(flags: &[Self]) -> Vec<String> {
+        flags
+            .iter()
+            .find_map(|flag| match flag {
+                Self::Apps(Value(Some(apps))) => Some(
+                    apps.split(',')
+                        .map(|app| app.trim().to_string())
+                        .filter(|app| !app.is_empty())
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn wants_devcontainer(flags: &[Self]) -> bool {
+        flags.contains(&Self::DevContainer)
+    }
+
+    pub fn skips_git(flags: &[Self]) -> bool {
+        flags.contains(&Self::NoGit)
+    }
+
+    /// Whether a git repository should be initialised even inside an existing one, see the `--git` flag.
+    pub fn forces_git(flags: &[Self]) -> bool {
+        flags.contains(&Self::Git)
+    }
+
+    /// Whether defaults should be accepted instead of prompting, see the `--yes` flag.
+    pub fn is_non_interactive(flags: &[Self]) -> bool {
+        flags.contains(&Self::Yes)
+    }
+
+    /// Value of the first flag matching `is_flag`, parsed as a whole number.
+    fn get_number(flags: &[Self], is_flag: impl Fn(&Self) -> bool) -> Option<u32> {
+        flags
+            .iter()
+            .filter(|flag| is_flag(flag))
+            .find_map(|flag| match flag {
+                Self::Timeout(Value(Some(value)))
+                | Self::Retries(Value(Some(value)))
+                | Self::Backoff(Value(Some(value))) => value.parse().ok(),
+                _ => None,
+            })
+    }
+
+    fn get_run_mode(flags: &[Self]) -> RunMode {
+        if flags.contains(&Self::NoRun) {
+            return RunMode::Skip;
+        }
+
+        let run = flags.iter().find(|flag| matches!(flag, Self::Run(_)));
+        match run {
+            Some(Self::Run(Value(Some(mode)))) if mode == "background" => RunMode::Background,
+            _ => RunMode::Foreground,
+        }
+    }
+
     pub fn handle_help_flag(prog_args: &ProgramArguments) -> DidSomething {
         if prog_args.get_flags().contains(&Self::Help) {
             if let Some(project_type) = prog_args.get_project_type() {
@@ -594,4 +1581,120 @@ mod tests {
         let invalid_raw_args = [String::from("invalid-option")].into_iter();
         ProgramArguments::build(invalid_raw_args).unwrap();
     }
+
+    #[test]
+    fn run_flags_map_to_run_modes() {
+        let to_flags = |raw: &[&str]| {
+            ProgramArguments::build(raw.iter().map(|s| s.to_string()))
+                .unwrap()
+                .flags
+        };
+
+        assert_eq!(Flag::get_run_mode(&to_flags(&["web"])), RunMode::Foreground);
+        assert_eq!(
+            Flag::get_run_mode(&to_flags(&["web", "--run"])),
+            RunMode::Foreground
+        );
+        assert_eq!(
+            Flag::get_run_mode(&to_flags(&["web", "--run=background"])),
+            RunMode::Background
+        );
+        assert_eq!(
+            Flag::get_run_mode(&to_flags(&["web", "--no-run"])),
+            RunMode::Skip
+        );
+    }
+
+    #[test]
+    fn retry_flags_override_project_defaults() {
+        let raw_args = ["django", "--timeout=0", "--retries=4", "--backoff=1"];
+        let args = ProgramArguments::build(raw_args.iter().map(|s| s.to_string())).unwrap();
+        let policy = RetryPolicy::build(&ProjectType::Django, args.get_flags());
+
+        assert_eq!(policy, RetryPolicy::new(None, 4, Duration::from_secs(1)));
+        assert_eq!(policy.backoff_before(2), Duration::from_secs(1));
+        assert_eq!(policy.backoff_before(4), Duration::from_secs(4));
+        assert!(
+            ProgramArguments::build(["web", "--retries=many"].iter().map(|s| s.to_string()))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn value_flags_accept_next_argument_and_keep_case() {
+        let raw_args = [
+            "--dir",
+            "../My Projects",
+            "Django",
+            "--name=MyApp",
+            "--force",
+        ];
+        let args = ProgramArguments::build(raw_args.iter().map(|s| s.to_string())).unwrap();
+
+        assert_eq!(args.get_project_type(), &Some(ProjectType::Django));
+        assert_eq!(
+            Flag::get_target_dir(args.get_flags()),
+            Some("../My Projects".to_string())
+        );
+        assert_eq!(
+            Flag::get_project_name(args.get_flags()),
+            Some("MyApp".to_string())
+        );
+        assert_eq!(
+            Flag::get_force_mode(args.get_flags()),
+            ForceMode::AllowEmpty
+        );
+        assert!(ProgramArguments::build(["web", "--dir"].iter().map(|s| s.to_string())).is_err());
+    }
+
+    #[test]
+    fn invalid_run_mode_returns_error() {
+        let raw_args = ["web", "--run=sideways"].into_iter().map(|s| s.to_string());
+        assert!(ProgramArguments::build(raw_args).is_err());
+    }
+
+    #[test]
+    fn package_manager_flag_picks_the_commands_run() {
+        let raw_args = ["astro", "-pm", "PNPM", "-y"]
+            .into_iter()
+            .map(|s| s.to_string());
+        let args = ProgramArguments::build(raw_args).unwrap();
+        let package_manager = Flag::get_package_manager(args.get_flags());
+
+        assert_eq!(package_manager, PackageManager::Pnpm);
+        assert!(Flag::is_non_interactive(args.get_flags()));
+        assert_eq!(
+            package_manager.exec_cmd("create-astro@latest", ". --no-install"),
+            "pnpm dlx create-astro@latest . --no-install"
+        );
+        assert_eq!(package_manager.add_cmd("tsx", true), "pnpm add -D tsx");
+        assert_eq!(Flag::get_package_manager(&[]), PackageManager::Npm);
+
+        let raw_args = ["astro", "--package-manager=deno"]
+            .into_iter()
+            .map(|s| s.to_string());
+        assert!(ProgramArguments::build(raw_args).is_err());
+    }
+
+    #[test]
+    fn open_flag_picks_the_opener() {
+        let to_flags = |raw: &[&str]| {
+            ProgramArguments::build(raw.iter().map(|s| s.to_string())).map(|args| args.flags)
+        };
+
+        assert_eq!(Flag::get_opener(&to_flags(&["web"]).unwrap()), None);
+        assert_eq!(
+            Flag::get_opener(&to_flags(&["web", "-o"]).unwrap()),
+            Some(Opener::Detect)
+        );
+        assert_eq!(
+            Flag::get_opener(&to_flags(&["web", "--open=Files"]).unwrap()),
+            Some(Opener::FileManager)
+        );
+        assert_eq!(
+            Flag::get_opener(&to_flags(&["web", "--open=none"]).unwrap()),
+            Some(Opener::Off)
+        );
+        assert!(to_flags(&["web", "--open=notepad"]).is_err());
+    }
 }
@@ -0,0 +1,211 @@
+//! Dev container configuration, see the `--devcontainer` flag and the `DevContainer` stage.
+//! The container is built from the image of the project type's first runtime (`ProjectType::runtimes`), pinned to the
+//! version found by the tooling checks, with its other runtimes added as features. A Dockerfile is only written when
+//! the project needs system packages on top (`ProjectType::system_packages`).
+
+use crate::{
+    data::Flag,
+    pipeline::{DevCommands, ProjectFile, SetUp},
+};
+
+const IMAGES: &str = "mcr.microsoft.com/devcontainers";
+const FEATURES: &str = "ghcr.io/devcontainers/features";
+
+/// Files of the project's dev container: its devcontainer.json, and a Dockerfile if it needs one.
+pub fn files(set_up: &SetUp) -> Vec<ProjectFile> {
+    let project_type = set_up.project_type;
+    let (runtime, other_runtimes) = project_type
+        .runtimes()
+        .split_first()
+        .unwrap_or((&"node", &[]));
+    let (image, mut features) = match *runtime {
+        "python" => (
+            format!(
+                "python:{}",
+                set_up.tool_versions.pinned_or("python", 2, "3")
+            ),
+            vec![],
+        ),
+        "node" => (
+            format!(
+                "javascript-node:{}",
+                set_up.tool_versions.pinned_or("node", 1, "22")
+            ),
+            vec![],
+        ),
+        "go" => (
+            format!("go:{}", set_up.tool_versions.pinned_or("go", 2, "1")),
+            vec![],
+        ),
+        // the rust image doesn't pin a toolchain, the feature it's made of does
+        _ => (String::from("base:bookworm"), vec![feature(set_up, "rust")]),
+    };
+    features.extend(
+        other_runtimes
+            .iter()
+            .map(|runtime| feature(set_up, runtime)),
+    );
+
+    let mut files = vec![];
+    let mut config = vec![format!("  \"name\": {}", json_string(&set_up.proj_name))];
+    let packages = project_type.system_packages();
+    if packages.is_empty() {
+        config.push(format!("  \"image\": \"{IMAGES}/{image}\""));
+    } else {
+        config.push(String::from(
+            "  \"build\": {\n    \"dockerfile\": \"Dockerfile\"\n  }",
+        ));
+        files.push(ProjectFile::new(
+            ".devcontainer/Dockerfile",
+            format!(
+                "FROM {IMAGES}/{image}\n\nRUN apt-get update \\\n    && export DEBIAN_FRONTEND=noninteractive \\\n    && apt-get install -y --no-install-recommends {} \\\n    && rm -rf /var/lib/apt/lists/*\n",
+                packages.join(" ")
+            ),
+        ));
+    }
+    if !features.is_empty() {
+        let features: Vec<String> = features
+            .iter()
+            .map(|(id, version)| {
+                format!("    \"{id}\": {{\n      \"version\": \"{version}\"\n    }}")
+            })
+            .collect();
+        config.push(format!(
+            "  \"features\": {{\n{}\n  }}",
+            features.join(",\n")
+        ));
+    }
+
+    let ports = forwarded_ports(set_up);
+    if !ports.is_empty() {
+        let ports: Vec<String> = ports.iter().map(u16::to_string).collect();
+        config.push(format!("  \"forwardPorts\": [{}]", ports.join(", ")));
+    }
+    if let Some(cmd) = post_create_command(set_up) {
+        config.push(format!("  \"postCreateCommand\": {}", json_string(&cmd)));
+    }
+
+    let extensions: Vec<String> = project_type
+        .editor_extensions(set_up)
+        .iter()
+        .map(|extension| format!("        \"{extension}\""))
+        .collect();
+    let mut vscode = vec![format!(
+        "      \"extensions\": [\n{}\n      ]",
+        extensions.join(",\n")
+    )];
+    if let Some(interpreter) = python_interpreter(set_up) {
+        vscode.push(format!(
+            "      \"settings\": {{\n        \"python.defaultInterpreterPath\": \"{interpreter}\"\n      }}"
+        ));
+    }
+    config.push(format!(
+        "  \"customizations\": {{\n    \"vscode\": {{\n{}\n    }}\n  }}",
+        vscode.join(",\n")
+    ));
+
+    files.push(ProjectFile::new(
+        ".devcontainer/devcontainer.json",
+        format!("{{\n{}\n}}\n", config.join(",\n")),
+    ));
+    files
+}
+
+/// The feature adding `runtime` to an image, with the version it's pinned to.
+fn feature(set_up: &SetUp, runtime: &str) -> (String, String) {
+    let version = match runtime {
+        "python" => set_up.tool_versions.pinned_or("python", 2, "latest"),
+        "node" => set_up.tool_versions.pinned_or("node", 1, "lts"),
+        "go" => set_up.tool_versions.pinned_or("go", 2, "latest"),
+        _ => set_up.tool_versions.pinned_or("rustc", 3, "latest"),
+    };
+    (format!("{FEATURES}/{runtime}:1"), version)
+}
+
+/// Ports of the project's dev servers, its parts' included.
+fn forwarded_ports(set_up: &SetUp) -> Vec<u16> {
+    let project_type = set_up.project_type;
+    let mut ports: Vec<u16> = project_type.dev_server_port().into_iter().collect();
+    for (_, part_type) in project_type.parts() {
+        if let Some(port) = part_type
+            .dev_server_port()
+            .filter(|port| !ports.contains(port))
+        {
+            ports.push(port);
+        }
+    }
+    ports
+}
+
+/// The project's setup commands, run once the container is created, as they are in its README.
+fn post_create_command(set_up: &SetUp) -> Option<String> {
+    let project_type = set_up.project_type;
+    let mut cmds = vec![];
+    if project_type.runtimes().contains(&"node") {
+        if let Some(cmd) = Flag::get_package_manager(set_up.flags).setup_cmd() {
+            cmds.push(cmd.to_string());
+        }
+    }
+    cmds.extend(setup_cmds(&project_type.dev_commands(set_up)));
+    for (folder, part_type) in project_type.parts() {
+        let part_cmds = setup_cmds(&part_type.dev_commands(set_up));
+        if !part_cmds.is_empty() {
+            cmds.push(format!("(cd {folder} && {})", part_cmds.join(" && ")));
+        }
+    }
+
+    (!cmds.is_empty()).then(|| cmds.join(" && "))
+}
+
+fn setup_cmds(commands: &DevCommands) -> Vec<String> {
+    let mut cmds = vec![];
+    if commands.venv {
+        cmds.extend([
+            String::from("python -m venv env"),
+            String::from(". env/bin/activate"),
+        ]);
+    }
+    cmds.extend(commands.setup.iter().cloned());
+    cmds
+}
+
+/// The python of the project's virtual environment, for the editor to use.
+fn python_interpreter(set_up: &SetUp) -> Option<String> {
+    let project_type = set_up.project_type;
+    if project_type.dev_commands(set_up).venv {
+        return Some(String::from("env/bin/python"));
+    }
+    project_type
+        .parts()
+        .iter()
+        .find(|(_, part_type)| part_type.dev_commands(set_up).venv)
+        .map(|(folder, _)| format!("{folder}/env/bin/python"))
+}
+
+fn json_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::ProjectType;
+
+    #[test]
+    fn fullstack_dev_container_has_both_runtimes_and_parts_set_up() {
+        let mut set_up = SetUp::new(ProjectType::Fullstack, &[]);
+        set_up.proj_name = String::from("shop");
+        let files = files(&set_up);
+
+        assert_eq!(files.len(), 1);
+        let config = &files[0].contents;
+        assert!(config.contains("\"name\": \"shop\""));
+        assert!(config.contains("\"image\": \"mcr.microsoft.com/devcontainers/python:3\""));
+        assert!(config.contains(
+            "\"ghcr.io/devcontainers/features/node:1\": {\n      \"version\": \"lts\"\n    }"
+        ));
+        assert!(config.contains("\"forwardPorts\": [5173, 8000]"));
+        assert!(config.contains("(cd backend && python -m venv env && . env/bin/activate && pip install django django-cors-headers)"));
+        assert!(config.contains("\"python.defaultInterpreterPath\": \"backend/env/bin/python\""));
+    }
+}
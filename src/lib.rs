@@ -1,16 +1,32 @@
 //! main binary functions
-//! all data types and their implementations are in the `data` module
+//! all data types and their implementations are in the `data` module,
+//! except for project set-up: the stages shared by all project types are in `pipeline`, and what each project type does in them is in `recipes`
 
+mod ci;
+mod config;
 mod constants;
 mod data;
+mod devcontainer;
+mod license;
+mod lint;
+mod manifest;
+mod naming;
+mod pipeline;
+mod readme;
+mod recipes;
+mod signals;
+mod templates;
+mod testing;
 mod utils;
 
+pub use constants::ABORTED_EXIT_CODE;
+pub use signals::install_handlers as install_signal_handlers;
 pub use utils::{clear_terminal, red_log, yellow_log};
 
-use std::env;
+use std::{env, fs};
 
-use data::{DidSomething, Flag, ProgramArguments, ProgramError};
-use utils::PEResult;
+use data::{Action, DidSomething, Flag, ProgramArguments, ProgramError};
+use utils::{green_log, prompt_input, PEResult};
 
 pub fn get_program_args() -> PEResult<ProgramArguments> {
     let mut raw_args = env::args();
@@ -25,8 +41,15 @@ pub fn run_program(args: ProgramArguments) -> PEResult<&'static str> {
 
     let project_type = args.get_project_type();
     if let Some(project_type) = project_type {
-        project_type.check_for_required_tooling(&args.get_flags())?;
-        project_type.set_up(args.get_flags())?;
+        let flags = match args.get_action() {
+            Action::SetUp => args.get_flags().clone(),
+            Action::AddApp => Flag::with_workspace_package_manager(args.get_flags()),
+        };
+        let tool_versions = project_type.check_for_required_tooling(&flags)?;
+        match args.get_action() {
+            Action::SetUp => project_type.set_up(&flags, tool_versions)?,
+            Action::AddApp => project_type.add_app(&flags, tool_versions)?,
+        }
         Ok("DONE")
     } else {
         Err(ProgramError::new(
@@ -34,3 +57,23 @@ pub fn run_program(args: ProgramArguments) -> PEResult<&'static str> {
         ))
     }
 }
+
+/// Offers to remove the partially built project folder, after the user aborted set-up.
+pub fn clean_up_after_abort() {
+    let Some(proj_dir) = signals::partial_project() else {
+        return;
+    };
+
+    let answer = prompt_input(
+        format!("Set-up was aborted. Remove the partial project at {proj_dir:?}? (y/N): ").as_str(),
+    );
+
+    if answer.is_ok_and(|answer| answer.trim().eq_ignore_ascii_case("y")) {
+        match fs::remove_dir_all(&proj_dir) {
+            Ok(_) => green_log(format!("removed {proj_dir:?}").as_str()),
+            Err(e) => red_log(format!("Failed to remove {proj_dir:?}: {e}").as_str()),
+        }
+    } else {
+        yellow_log(format!("kept partial project at {proj_dir:?}").as_str());
+    }
+}
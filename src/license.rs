@@ -0,0 +1,183 @@
+//! License files, see the `--license` flag.
+//! The license texts are bundled into the binary (see `templates`), so a LICENSE can be written offline, and the SPDX
+//! id of the license is set in whichever manifests the project type has (`ProjectType::license_manifests`).
+
+use std::{
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{constants::VALID_LICENSES, manifest::indent_of, templates};
+
+/// The SPDX id `value` names, ignoring case. The deprecated bare GNU ids (e.g. `GPL-3.0`) are taken to mean the
+/// `-only` licenses, as SPDX defines them.
+pub fn spdx_id(value: &str) -> Option<&'static str> {
+    let value = value.trim();
+    let value = match value.to_uppercase().as_str() {
+        "GPL-2.0" | "GPL-3.0" | "LGPL-3.0" => format!("{value}-only"),
+        _ => value.to_string(),
+    };
+    VALID_LICENSES
+        .iter()
+        .find(|id| id.eq_ignore_ascii_case(&value))
+        .copied()
+}
+
+/// Text of the LICENSE file for the license `id` (one of `VALID_LICENSES`), with `author` as the copyright holder
+/// where the license names one.
+pub fn render(id: &str, author: &str) -> String {
+    let text = match id {
+        "Apache-2.0" => templates::LICENSE_APACHE_2_0,
+        "GPL-2.0-only" | "GPL-2.0-or-later" => templates::LICENSE_GPL_2_0,
+        "GPL-3.0-only" | "GPL-3.0-or-later" => templates::LICENSE_GPL_3_0,
+        "LGPL-3.0-only" | "LGPL-3.0-or-later" => templates::LICENSE_LGPL_3_0,
+        "BSD-2-Clause" => templates::LICENSE_BSD_2_CLAUSE,
+        "BSD-3-Clause" => templates::LICENSE_BSD_3_CLAUSE,
+        "ISC" => templates::LICENSE_ISC,
+        "MPL-2.0" => templates::LICENSE_MPL_2_0,
+        "Unlicense" => templates::LICENSE_UNLICENSE,
+        _ => templates::LICENSE_MIT,
+    };
+    let days_since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs() / 86_400);
+
+    templates::render(
+        text,
+        &[
+            ("year", &year_of(days_since_epoch as i64).to_string()),
+            ("author", author),
+        ],
+    )
+}
+
+/// `contents` of the manifest at `path` with its license field set to `id`, or `None` if it isn't a manifest that is
+/// understood: a `package.json`, or a `Cargo.toml` with a `[package]` or `pyproject.toml` with a `[project]` table.
+pub fn set_manifest_license(path: &str, contents: &str, id: &str) -> Option<String> {
+    let edited = match Path::new(path).file_name()?.to_str()? {
+        "package.json" => set_json_license(contents, id),
+        "Cargo.toml" => set_toml_license(contents, "package", id),
+        "pyproject.toml" => set_toml_license(contents, "project", id),
+        _ => None,
+    }?;
+
+    Some(if contents.ends_with('\n') {
+        format!("{edited}\n")
+    } else {
+        edited
+    })
+}
+
+/// Sets the top level `"license"` key, adding it after `"name"` if missing. Top level keys are told apart from nested
+/// ones by their indentation, the same as the `"name"` key's.
+fn set_json_license(contents: &str, id: &str) -> Option<String> {
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+    let name = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with("\"name\""))?;
+    let indent = indent_of(&lines[name]);
+    let field = format!("{indent}\"license\": \"{id}\"");
+
+    let license = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with("\"license\"") && indent_of(line) == indent);
+    match license {
+        Some(license) => {
+            let comma = if lines[license].trim_end().ends_with(',') {
+                ","
+            } else {
+                ""
+            };
+            lines[license] = format!("{field}{comma}");
+        }
+        // the name is never the only key, so it's followed by a comma
+        None if lines[name].trim_end().ends_with(',') => {
+            lines.insert(name + 1, format!("{field},"))
+        }
+        None => return None,
+    }
+
+    Some(lines.join("\n"))
+}
+
+/// Sets `license` in the `[table]` table, adding it after the table's last key if missing.
+fn set_toml_license(contents: &str, table: &str, id: &str) -> Option<String> {
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+    let header = format!("[{table}]");
+
+    let start = lines.iter().position(|line| line.trim() == header)? + 1;
+    let end = lines[start..]
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .map_or(lines.len(), |i| start + i);
+    let field = format!("license = \"{id}\"");
+
+    let license = (start..end).find(|&i| {
+        let key = lines[i].split('=').next().unwrap_or_default().trim();
+        key == "license" || key.starts_with("license.")
+    });
+    match license {
+        Some(license) => lines[license] = field,
+        None => {
+            let last_key = (start..end)
+                .rev()
+                .find(|&i| !lines[i].trim().is_empty())
+                .unwrap_or(start - 1);
+            lines.insert(last_key + 1, field);
+        }
+    }
+
+    Some(lines.join("\n"))
+}
+
+/// The (proleptic gregorian) year a number of days since 1970-01-01 falls in, see
+/// http://howardhinnant.github.io/date_algorithms.html#civil_from_days.
+fn year_of(days_since_epoch: i64) -> i64 {
+    let days = days_since_epoch + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // months counted from march, so january and february fall in the next year
+    let month = (5 * day_of_year + 2) / 153;
+
+    year_of_era + era * 400 + i64::from(month >= 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn license_is_set_in_manifests_and_ids_are_normalised() {
+        assert_eq!(spdx_id("mit"), Some("MIT"));
+        assert_eq!(spdx_id("GPL-3.0"), Some("GPL-3.0-only"));
+        assert_eq!(spdx_id("WTFPL"), None);
+        assert_eq!(year_of(0), 1970);
+        assert_eq!(year_of(19_722), 2023);
+        assert_eq!(year_of(19_723), 2024);
+
+        let package_json = "{\n  \"name\": \"app\",\n  \"license\": \"ISC\",\n  \"dependencies\": {\n    \"license\": \"^1.0.0\"\n  }\n}\n";
+        assert_eq!(
+            set_manifest_license("package.json", package_json, "MIT").unwrap(),
+            package_json.replacen("ISC", "MIT", 1)
+        );
+        assert_eq!(
+            set_manifest_license(
+                "web/package.json",
+                "{\n  \"name\": \"app\",\n  \"private\": true\n}",
+                "MIT"
+            )
+            .unwrap(),
+            "{\n  \"name\": \"app\",\n  \"license\": \"MIT\",\n  \"private\": true\n}"
+        );
+
+        let cargo_toml = "[package]\nname = \"app\"\nedition = \"2021\"\n\n[dependencies]\n";
+        assert_eq!(
+            set_manifest_license("Cargo.toml", cargo_toml, "Apache-2.0").unwrap(),
+            "[package]\nname = \"app\"\nedition = \"2021\"\nlicense = \"Apache-2.0\"\n\n[dependencies]\n"
+        );
+        assert!(set_manifest_license("Cargo.toml", "[workspace]\n", "MIT").is_none());
+    }
+}
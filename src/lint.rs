@@ -0,0 +1,85 @@
+//! Merges the `--lint` preset's settings into the config files the generators wrote, keeping what's already set.
+
+use crate::{manifest, templates};
+
+/// Files matched by lint-staged, and what is run on them before each commit.
+const ESLINT_LINT_STAGED: &str = "{\n  \"*.{js,jsx,ts,tsx,mjs,cjs}\": [\"eslint --fix\", \"prettier --write\"],\n  \"*.{json,css,md,html,yaml,yml}\": \"prettier --write\"\n}";
+const BIOME_LINT_STAGED: &str =
+    "{\n  \"*\": \"biome check --write --no-errors-on-unmatched --files-ignore-unknown=true\"\n}";
+
+/// package.json with the scripts and lint-staged config of the ESLint and Prettier preset.
+pub fn eslint_package_json(package_json: &str) -> Option<String> {
+    let package_json = manifest::set_scripts(
+        package_json,
+        &[("format", "prettier --write ."), ("prepare", "husky")],
+        &[],
+    )?;
+    manifest::add_top_level_key(&package_json, "lint-staged", ESLINT_LINT_STAGED)
+}
+
+/// package.json with the scripts and lint-staged config of the Biome preset, which takes over the lint script.
+pub fn biome_package_json(package_json: &str) -> Option<String> {
+    let package_json = manifest::set_scripts(
+        package_json,
+        &[
+            ("lint", "biome check ."),
+            ("format", "biome check --write ."),
+            ("prepare", "husky"),
+        ],
+        &["lint"],
+    )?;
+    manifest::add_top_level_key(&package_json, "lint-staged", BIOME_LINT_STAGED)
+}
+
+/// A flat eslint config with eslint-config-prettier added last, so it turns off the rules Prettier takes care of.
+pub fn eslint_config_with_prettier(config: &str) -> Option<String> {
+    if config.contains("eslint-config-prettier") {
+        return Some(config.to_string());
+    }
+
+    // whatever is exported, e.g. `defineConfig([...])` or `tseslint.config(...)`, is an array of configs
+    let (body, exported) = config.split_once("export default ")?;
+    let exported = exported.trim_end().trim_end_matches(';');
+    Some(format!(
+        "import eslintConfigPrettier from \"eslint-config-prettier/flat\";\n{body}export default [\n  ...{exported},\n  eslintConfigPrettier,\n];\n"
+    ))
+}
+
+/// pyproject.toml with the ruff and black settings of the ruff preset, those of its tables already there kept as
+/// they are.
+pub fn ruff_pyproject(pyproject: &str) -> Option<String> {
+    Some(manifest::add_toml_tables(
+        pyproject,
+        templates::LINT_PYPROJECT_TOOLS_TOML,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_merged_into_existing_config() {
+        let package_json = "{\n  \"name\": \"app\",\n  \"scripts\": {\n    \"dev\": \"vite\",\n    \"lint\": \"eslint .\"\n  },\n  \"devDependencies\": {\n    \"vite\": \"^7.0.0\"\n  }\n}\n";
+        let merged = eslint_package_json(package_json).unwrap();
+        assert!(merged.contains("    \"lint\": \"eslint .\",\n    \"format\": \"prettier --write .\",\n    \"prepare\": \"husky\"\n  },"));
+        assert!(merged.contains("  },\n  \"lint-staged\": {\n    \"*.{js,jsx,ts,tsx,mjs,cjs}\": [\"eslint --fix\", \"prettier --write\"],"));
+        assert!(merged.ends_with("\"prettier --write\"\n  }\n}\n"));
+        assert_eq!(eslint_package_json(&merged).unwrap(), merged);
+        assert!(biome_package_json(package_json)
+            .unwrap()
+            .contains("    \"lint\": \"biome check .\",\n"));
+
+        let eslint_config = "import js from '@eslint/js'\n\nexport default defineConfig([\n  js.configs.recommended,\n])\n";
+        let merged = eslint_config_with_prettier(eslint_config).unwrap();
+        assert!(merged.starts_with("import eslintConfigPrettier from"));
+        assert!(merged.ends_with("export default [\n  ...defineConfig([\n  js.configs.recommended,\n]),\n  eslintConfigPrettier,\n];\n"));
+        assert_eq!(eslint_config_with_prettier(&merged).unwrap(), merged);
+
+        let pyproject = "[project]\nname = \"app\"\n\n[tool.black]\nline-length = 100\n";
+        let merged = ruff_pyproject(pyproject).unwrap();
+        assert!(merged.contains("[tool.ruff]\n") && merged.contains("[tool.ruff.lint]\n"));
+        assert_eq!(merged.matches("[tool.black]").count(), 1);
+        assert!(merged.contains("line-length = 100"));
+    }
+}
@@ -11,6 +11,7 @@
 
 use std::process;
 
+use plateboiler::{clean_up_after_abort, install_signal_handlers, ABORTED_EXIT_CODE};
 use plateboiler::{clear_terminal, red_log, yellow_log};
 use plateboiler::{get_program_args, run_program};
 
@@ -18,6 +19,7 @@ const ERROR_EXIT_CODE: i32 = 0; // Not an error exit code, I know. Using it so t
 
 fn main() {
     clear_terminal();
+    install_signal_handlers();
     yellow_log("-----------------------------------------");
 
     let args = match get_program_args() {
@@ -30,11 +32,16 @@ fn main() {
 
     match run_program(args) {
         Ok(msg) => println!("{msg}"),
+        Err(e) if e.is_aborted() => {
+            red_log(format!("Aborted: {}", e.msg()).as_str());
+            clean_up_after_abort();
+            process::exit(ABORTED_EXIT_CODE)
+        }
         Err(e) => {
             red_log(format!("Error: {} \nExiting...", e.msg()).as_str());
             process::exit(ERROR_EXIT_CODE)
         }
     }
 
-    println!("");
+    println!();
 }
@@ -0,0 +1,104 @@
+//! Edits to package.json and pyproject.toml, made line by line so the formatting of the files is kept. Used to merge
+//! settings into the manifests the generators wrote, see the `lint` and `testing` modules.
+
+/// `pyproject` with the tables of `tables` (blank line separated, each starting with its header) added, except those
+/// already there, which are kept as they are.
+pub fn add_toml_tables(pyproject: &str, tables: &str) -> String {
+    let missing: Vec<&str> = tables
+        .trim()
+        .split("\n\n")
+        .filter(|table| {
+            let header = table.lines().next().unwrap_or_default().trim();
+            !pyproject.lines().any(|line| line.trim() == header)
+        })
+        .collect();
+    if missing.is_empty() {
+        return pyproject.to_string();
+    }
+
+    format!("{}\n\n{}\n", pyproject.trim_end(), missing.join("\n\n"))
+}
+
+/// Adds `scripts` to package.json's "scripts", keeping those already there unless they are named in `replace`.
+pub fn set_scripts(contents: &str, scripts: &[(&str, &str)], replace: &[&str]) -> Option<String> {
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+
+    let start = lines.iter().position(|line| {
+        line.trim_start().starts_with("\"scripts\"") && line.trim_end().ends_with('{')
+    })?;
+    let indent = indent_of(&lines[start]);
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.trim_start().starts_with('}') && indent_of(line) == indent)?;
+    let key_indent = match lines.get(start + 1) {
+        Some(line) if start + 1 < end => indent_of(line),
+        _ => format!("{indent}  "),
+    };
+
+    let mut missing = vec![];
+    for (name, cmd) in scripts {
+        let field = format!("{key_indent}\"{name}\": \"{cmd}\"");
+        let key = format!("\"{name}\"");
+        match (start + 1..end).find(|&i| lines[i].trim_start().starts_with(&key)) {
+            Some(i) if replace.contains(name) => {
+                let comma = if lines[i].trim_end().ends_with(',') {
+                    ","
+                } else {
+                    ""
+                };
+                lines[i] = format!("{field}{comma}");
+            }
+            Some(_) => {}
+            None => missing.push(field),
+        }
+    }
+    if missing.is_empty() {
+        return Some(join_lines(lines, contents));
+    }
+
+    if end > start + 1 && !lines[end - 1].trim_end().ends_with(',') {
+        lines[end - 1].push(',');
+    }
+    lines.splice(end..end, [missing.join(",\n")]);
+
+    Some(join_lines(lines, contents))
+}
+
+/// Adds the top level `key` to a JSON object, with `value` indented as a top level value, unless it's already there.
+pub fn add_top_level_key(contents: &str, key: &str, value: &str) -> Option<String> {
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+
+    let close = lines.iter().rposition(|line| line.trim() == "}")?;
+    let indent = lines[..close]
+        .iter()
+        .find(|line| line.trim_start().starts_with('"'))
+        .map_or(String::from("  "), |line| indent_of(line));
+    let field = format!("{indent}\"{key}\"");
+    if lines.iter().any(|line| line.starts_with(&field)) {
+        return Some(contents.to_string());
+    }
+
+    let last = (0..close).rev().find(|&i| !lines[i].trim().is_empty())?;
+    if !lines[last].trim_end().ends_with([',', '{']) {
+        lines[last].push(',');
+    }
+    let value = value.replace('\n', &format!("\n{indent}"));
+    lines.insert(close, format!("{field}: {value}"));
+
+    Some(join_lines(lines, contents))
+}
+
+pub(crate) fn indent_of(line: &str) -> String {
+    line[..line.len() - line.trim_start().len()].to_string()
+}
+
+/// `lines` as the contents of a file, ending with a newline if `original` did.
+fn join_lines(lines: Vec<String>, original: &str) -> String {
+    let joined = lines.join("\n");
+    if original.ends_with('\n') {
+        format!("{joined}\n")
+    } else {
+        joined
+    }
+}
@@ -0,0 +1,357 @@
+//! Project naming rules.
+//! The project name is used as the folder name and handed to upstream tools, so each project type declares the rules
+//! of its ecosystem, letting bad names be caught up front instead of failing half way through set-up.
+
+/// Node.js core modules and other names npm won't publish or install as a package.
+const NPM_RESERVED_NAMES: [&str; 32] = [
+    "node_modules",
+    "favicon.ico",
+    "assert",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "dns",
+    "domain",
+    "events",
+    "fs",
+    "http",
+    "https",
+    "module",
+    "net",
+    "os",
+    "path",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "string_decoder",
+    "timers",
+    "tls",
+    "tty",
+    "url",
+    "util",
+];
+
+/// Python keywords, and module names a project package would shadow or django-admin refuses.
+const PYTHON_RESERVED_NAMES: [&str; 44] = [
+    "false", "none", "true", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield", "django", "test", "tests", "site", "os", "sys", "json", "logging", "types",
+];
+
+/// Rust keywords, and crate names that clash with the standard crates or cargo's own targets.
+const CRATE_RESERVED_NAMES: [&str; 44] = [
+    "as",
+    "async",
+    "await",
+    "break",
+    "const",
+    "continue",
+    "crate",
+    "dyn",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "fn",
+    "for",
+    "if",
+    "impl",
+    "in",
+    "let",
+    "loop",
+    "match",
+    "mod",
+    "move",
+    "mut",
+    "pub",
+    "ref",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "super",
+    "trait",
+    "true",
+    "type",
+    "unsafe",
+    "use",
+    "where",
+    "while",
+    "std",
+    "core",
+    "alloc",
+    "proc_macro",
+    "proc-macro",
+    "test",
+    "build",
+];
+
+/// Folder names the go tool gives a meaning of its own, or module paths it reserves.
+const GO_RESERVED_NAMES: [&str; 7] = [
+    "std", "cmd", "all", "main", "internal", "vendor", "testdata",
+];
+
+/// Longest name npm accepts for a package.
+const MAX_NAME_LENGTH: usize = 214;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NamingRules {
+    /// npm package names: lowercase, url-safe characters, not a Node.js core module.
+    NpmPackage,
+    /// Python identifiers that are not keywords and don't shadow common modules.
+    PythonIdentifier,
+    /// Cargo package names: letters, digits, '-' and '_', not a Rust keyword or standard crate.
+    Crate,
+    /// Go module path elements: lowercase letters, digits, '-', '.', '_' and '~'.
+    GoModule,
+}
+
+impl NamingRules {
+    /// Checks `name` against the rules, returning every problem found.
+    pub fn check(&self, name: &str) -> Result<(), Vec<String>> {
+        let mut problems = Self::check_folder_name(name);
+        if name.is_empty() {
+            return Err(problems);
+        }
+
+        match self {
+            NamingRules::NpmPackage => {
+                if name.to_lowercase() != name {
+                    problems.push("npm package names must be lowercase".to_string());
+                }
+                if name.starts_with('_') {
+                    problems.push("npm package names can't start with '_'".to_string());
+                }
+                if name
+                    .chars()
+                    .any(|c| !(c.is_ascii_alphanumeric() || "-._~".contains(c)))
+                {
+                    problems.push(
+                        "npm package names may only contain letters, digits, '-', '.', '_' and '~'"
+                            .to_string(),
+                    );
+                }
+                if NPM_RESERVED_NAMES.contains(&name.to_lowercase().as_str()) {
+                    problems.push(format!("'{name}' is reserved by Node.js/npm"));
+                }
+            }
+            NamingRules::PythonIdentifier => {
+                if name.starts_with(|c: char| c.is_ascii_digit()) {
+                    problems.push("Python identifiers can't start with a digit".to_string());
+                }
+                if name
+                    .chars()
+                    .any(|c| !(c.is_ascii_alphanumeric() || c == '_'))
+                {
+                    problems.push(
+                        "Python identifiers may only contain letters, digits and '_'".to_string(),
+                    );
+                }
+                if PYTHON_RESERVED_NAMES.contains(&name.to_lowercase().as_str()) {
+                    problems.push(format!(
+                        "'{name}' is a Python keyword or clashes with an existing module"
+                    ));
+                }
+            }
+            NamingRules::Crate => {
+                if name.starts_with(|c: char| c.is_ascii_digit()) {
+                    problems.push("crate names can't start with a digit".to_string());
+                }
+                if name
+                    .chars()
+                    .any(|c| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+                {
+                    problems.push(
+                        "crate names may only contain letters, digits, '-' and '_'".to_string(),
+                    );
+                }
+                if CRATE_RESERVED_NAMES.contains(&name.to_lowercase().as_str()) {
+                    problems.push(format!(
+                        "'{name}' is a Rust keyword or clashes with a standard crate"
+                    ));
+                }
+            }
+            NamingRules::GoModule => {
+                problems.extend(Self::check_go_path_element(name));
+                if GO_RESERVED_NAMES.contains(&name.to_lowercase().as_str()) {
+                    problems.push(format!("'{name}' is reserved by the go tool"));
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Normalises `name` into one that passes `check`, e.g. `My App` becomes `my-app` or `my_app`.
+    pub fn normalise(&self, name: &str) -> String {
+        let separator = match self {
+            NamingRules::NpmPackage | NamingRules::Crate | NamingRules::GoModule => '-',
+            NamingRules::PythonIdentifier => '_',
+        };
+
+        let mut normalised = String::new();
+        for c in name.trim().to_lowercase().chars() {
+            if c.is_ascii_alphanumeric() || (c == '_' && separator == '_') {
+                normalised.push(c);
+            } else if !normalised.ends_with(separator) {
+                normalised.push(separator);
+            }
+        }
+        let mut normalised = normalised
+            .trim_matches(|c| c == separator || c == '_')
+            .chars()
+            .take(MAX_NAME_LENGTH - 4)
+            .collect::<String>();
+
+        if normalised.is_empty() {
+            normalised = format!("my{separator}app");
+        }
+        if *self != NamingRules::NpmPackage && normalised.starts_with(|c: char| c.is_ascii_digit())
+        {
+            normalised = format!("app{separator}{normalised}");
+        }
+        if self.check(&normalised).is_err() {
+            normalised = format!("{normalised}{separator}app");
+        }
+
+        normalised
+    }
+
+    /// Checks a whole Go module path, e.g. `github.com/me/my-app`, element by element.
+    pub fn check_go_module_path(path: &str) -> Result<(), Vec<String>> {
+        if path.is_empty() {
+            return Err(vec!["the module path can't be empty".to_string()]);
+        }
+
+        let mut problems = vec![];
+        for element in path.split('/') {
+            if element.is_empty() {
+                problems.push("module path elements can't be empty".to_string());
+                continue;
+            }
+            if element.starts_with('.') {
+                problems.push(format!("'{element}' can't start with '.'"));
+            }
+            problems.extend(Self::check_go_path_element(element));
+        }
+        problems.dedup();
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    fn check_go_path_element(element: &str) -> Vec<String> {
+        let mut problems = vec![];
+        if element.to_lowercase() != element {
+            problems.push("go module paths should be lowercase".to_string());
+        }
+        if element.ends_with('.') {
+            problems.push("go module path elements can't end with '.'".to_string());
+        }
+        if element
+            .chars()
+            .any(|c| !(c.is_ascii_alphanumeric() || "-._~".contains(c)))
+        {
+            problems.push(
+                "go module paths may only contain letters, digits, '-', '.', '_' and '~'"
+                    .to_string(),
+            );
+        }
+        problems
+    }
+
+    /// Rules every project folder name must follow, whatever the ecosystem.
+    fn check_folder_name(name: &str) -> Vec<String> {
+        let mut problems = vec![];
+
+        if name.is_empty() {
+            problems.push("the name can't be empty".to_string());
+            return problems;
+        }
+        if name.len() > MAX_NAME_LENGTH {
+            problems.push(format!(
+                "the name can't be longer than {MAX_NAME_LENGTH} characters"
+            ));
+        }
+        if name.starts_with('.') {
+            problems.push("the name can't start with '.'".to_string());
+        }
+        if name.contains(char::is_whitespace) {
+            problems.push("the name can't contain spaces".to_string());
+        }
+        if name
+            .chars()
+            .any(|c| c.is_control() || "/\\<>:\"|?*".contains(c))
+        {
+            problems.push(
+                "the name can't contain path separators or characters like <>:\"|?*".to_string(),
+            );
+        }
+
+        problems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_names_are_rejected() {
+        for name in ["", "..", "My App", "a/b", "http", "_private", "Caps"] {
+            assert!(NamingRules::NpmPackage.check(name).is_err(), "{name:?}");
+        }
+        for name in ["", "..", "my-app", "1st", "django", "test", "class"] {
+            assert!(
+                NamingRules::PythonIdentifier.check(name).is_err(),
+                "{name:?}"
+            );
+        }
+        for name in ["", "my.app", "2d", "core", "fn"] {
+            assert!(NamingRules::Crate.check(name).is_err(), "{name:?}");
+        }
+        for name in ["My-App", "app.", "std", "a+b"] {
+            assert!(NamingRules::GoModule.check(name).is_err(), "{name:?}");
+        }
+        for path in ["", "github.com//x", "example.com/My-App", "/x", ".x/y"] {
+            assert!(NamingRules::check_go_module_path(path).is_err(), "{path:?}");
+        }
+        assert!(NamingRules::check_go_module_path("github.com/me/my-app.v2").is_ok());
+        assert!(NamingRules::Crate.check("My_crate-2").is_ok());
+        assert!(NamingRules::NpmPackage.check("my-app.v2").is_ok());
+        assert!(NamingRules::PythonIdentifier.check("My_App2").is_ok());
+    }
+
+    #[test]
+    fn names_are_normalised_per_ecosystem() {
+        assert_eq!(NamingRules::NpmPackage.normalise("My App"), "my-app");
+        assert_eq!(NamingRules::PythonIdentifier.normalise("My App"), "my_app");
+        assert_eq!(NamingRules::NpmPackage.normalise("../x"), "x");
+        assert_eq!(NamingRules::NpmPackage.normalise("http"), "http-app");
+        assert_eq!(NamingRules::Crate.normalise("2D Engine"), "app-2d-engine");
+        assert_eq!(
+            NamingRules::PythonIdentifier.normalise("django"),
+            "django_app"
+        );
+        assert_eq!(
+            NamingRules::PythonIdentifier.normalise("2fa site"),
+            "app_2fa_site"
+        );
+        assert_eq!(NamingRules::PythonIdentifier.normalise(""), "my_app");
+    }
+}
@@ -0,0 +1,1355 @@
+//! Project creation pipeline.
+//! Every set-up goes through the same stages, in the same order. A project type only declares which stages it uses
+//! (`ProjectType::stages`) and what they do for it (the other hooks in the `recipes` module).
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    ci,
+    config::Config,
+    constants::{VALID_OPENERS, VALID_PACKAGE_MANAGERS},
+    data::{
+        Flag, ForceMode, Opener, ProgramError, ProjectType, RetryPolicy, Terminal, ToolVersions,
+    },
+    devcontainer, license,
+    naming::NamingRules,
+    readme, signals, templates,
+    utils::{
+        green_log, prompt_choice, prompt_choices, prompt_input, red_log, run_seperate_cmd,
+        yellow_log, PEResult,
+    },
+};
+
+/// The stages a set-up can go through, run in the order the project type lists them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stage {
+    /// Gets the project name from `--name` or a prompt, and validates it.
+    ResolveName,
+    /// Picks the project type's variant and extras, from `--variant`/`--extras` or prompts, and its module path if it
+    /// has one, from the config or a prompt. Django projects also get their settings package and apps, see the
+    /// `--django-package` and `--apps` flags.
+    Configure,
+    /// Creates the project folder in the target directory.
+    PrepareDir,
+    /// Sets up each of the project type's parts (`ProjectType::parts`) in its own folder inside the project folder,
+    /// through the part's own `Configure`, `Generate`, `WriteFiles`, `Migrations`, `DetectRoot` and `Install` stages.
+    SetUpParts,
+    /// Runs the project type's generator steps inside the project folder.
+    Generate,
+    /// Writes the project type's file templates, leaving files that already exist alone, then applies its patches to
+    /// generated files.
+    WriteFiles,
+    /// Makes the initial migrations of the models the project was generated with (`ProjectType::migration_steps`), so
+    /// they are in place before the database is first migrated.
+    Migrations,
+    /// Finds the root of the generated project by the project type's marker file, and moves into it.
+    DetectRoot,
+    /// Installs dependencies, under the project type's retry policy.
+    Install,
+    /// Installs and configures the project type's test framework preset (`ProjectType::testing_preset`), with an
+    /// example test, which is run once with `--testing=run`. Skipped without `--testing`, and with a warning for web
+    /// projects whose vite app has no `src/App.tsx` for vitest's example test.
+    Testing,
+    /// Writes an .editorconfig, then installs and configures the project type's lint preset
+    /// (`ProjectType::lint_preset`), and formats the project with it. Skipped without `--lint`.
+    Lint,
+    /// Writes a Dockerfile, .dockerignore and compose.yaml for the project type (`ProjectType::docker_files`), and
+    /// edits generated files for them to work. Skipped without `--docker`.
+    Docker,
+    /// Writes a .devcontainer/devcontainer.json for the project, and a Dockerfile for it if the project type needs
+    /// system packages (see the `devcontainer` module). Skipped without `--devcontainer`.
+    DevContainer,
+    /// Writes a CI pipeline for the provider asked for with `--ci` (see the `ci` module). Skipped without `--ci`.
+    Ci,
+    /// Writes a README.md describing the project's stack, the tool versions found by the tooling checks, and the
+    /// commands to set it up, run it, test it and build it. A README written by a generator is kept, below it.
+    Readme,
+    /// Writes a LICENSE file for the license asked for with `--license`, and sets it in the project type's manifests
+    /// (`ProjectType::license_manifests`). Skipped without `--license`.
+    License,
+    /// Writes (or adds to) a .gitignore for the project type, then initialises a git repository in the project and makes
+    /// an initial commit. The repository is skipped inside an existing one unless `--git` is passed, and always with
+    /// `--no-git`.
+    InitGit,
+    /// Reports where the finished project is, and opens it if asked to (see the `--open` flag).
+    Finalise,
+    /// Runs the dev server, see the `--run` and `--no-run` flags. Last, as it usually blocks until Ctrl-C.
+    Run,
+}
+
+/// A command run by a stage.
+pub struct Step {
+    pub cmd: String,
+    pub err_msg: &'static str,
+    pub log_msg: &'static str,
+}
+
+/// A file written by the `WriteFiles` stage, `path` being relative to the project root.
+pub struct ProjectFile {
+    pub path: String,
+    pub contents: String,
+}
+
+/// Returns a file's edited contents, see `FilePatch`.
+type Edit = Box<dyn Fn(&str) -> Option<String>>;
+
+/// An edit to a file made by a generator, applied by the `WriteFiles` stage to the first of `paths` (relative to the
+/// project root) that exists. `apply` returns the edited contents, or `None` if it doesn't recognise the file, in which
+/// case `hint` is shown so the edit can be made by hand.
+pub struct FilePatch {
+    pub paths: Vec<String>,
+    pub apply: Edit,
+    pub hint: &'static str,
+}
+
+/// Commands a developer runs in the project by hand, from its root (see `ProjectType::dev_commands`). With `venv`, they
+/// are run with the project's python virtual environment (`env/`) activated.
+pub struct DevCommands {
+    pub venv: bool,
+    /// Sets up a fresh checkout of the project, once the virtual environment is created if it has one.
+    pub setup: Vec<String>,
+    pub dev: Option<String>,
+    /// Checks the code without running it, e.g. with a linter or a type checker.
+    pub lint: Option<String>,
+    pub test: Option<String>,
+    pub build: Option<String>,
+}
+
+/// State of a set-up as it moves through its stages.
+pub struct SetUp<'a> {
+    pub project_type: ProjectType,
+    pub flags: &'a [Flag],
+    pub proj_name: String,
+    pub proj_dir: PathBuf,
+    /// One of `ProjectType::variants`, if the project type has any.
+    pub variant: Option<&'static str>,
+    /// Those of `ProjectType::extras` that were picked.
+    pub extras: Vec<&'static str>,
+    /// Module path the project is published under, for project types that have one (see `ProjectType::module_prefix_key`).
+    pub module_path: Option<String>,
+    /// Apps created with startapp in django projects, see the `--apps` flag.
+    pub django_apps: Vec<String>,
+    pub config: Config,
+    /// Versions of the tools found by the tooling checks, before set-up started.
+    pub tool_versions: ToolVersions,
+    /// Test framework preset set up by the `Testing` stage, see `ProjectType::testing_preset`.
+    pub testing: Option<&'static str>,
+    /// Package django's settings are in, for django projects and projects with a django part. `None` for the default,
+    /// see `django_package`.
+    django_package: Option<String>,
+    /// Directory the project folder is created in, instead of the one picked by the flags (see `create_project_dir`).
+    target_dir: Option<PathBuf>,
+    /// Whether this sets up a part of another project, which resolves the extras and django apps it's handed.
+    is_part: bool,
+    terminal: Terminal,
+}
+
+impl Step {
+    pub fn new(cmd: impl Into<String>, err_msg: &'static str, log_msg: &'static str) -> Self {
+        Self {
+            cmd: cmd.into(),
+            err_msg,
+            log_msg,
+        }
+    }
+}
+
+impl ProjectFile {
+    pub fn new(path: impl Into<String>, contents: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            contents: contents.into(),
+        }
+    }
+}
+
+impl FilePatch {
+    pub fn new(
+        paths: &[&str],
+        apply: impl Fn(&str) -> Option<String> + 'static,
+        hint: &'static str,
+    ) -> Self {
+        Self {
+            paths: paths.iter().map(|path| path.to_string()).collect(),
+            apply: Box::new(apply),
+            hint,
+        }
+    }
+}
+
+impl<'a> SetUp<'a> {
+    pub fn new(project_type: ProjectType, flags: &'a [Flag]) -> Self {
+        Self {
+            project_type,
+            flags,
+            proj_name: String::new(),
+            proj_dir: PathBuf::new(),
+            variant: None,
+            extras: vec![],
+            module_path: None,
+            django_apps: vec![],
+            config: Config::load(),
+            tool_versions: ToolVersions::default(),
+            testing: None,
+            django_package: None,
+            target_dir: None,
+            is_part: false,
+            terminal: Terminal::new(PathBuf::new()),
+        }
+    }
+
+    pub fn with_tool_versions(mut self, tool_versions: ToolVersions) -> Self {
+        self.tool_versions = tool_versions;
+        self
+    }
+
+    /// Creates the project folder in `target_dir`, e.g. the `apps/` folder of a monorepo, whatever the flags say.
+    pub fn in_dir(mut self, target_dir: PathBuf) -> Self {
+        self.target_dir = Some(target_dir);
+        self
+    }
+
+    pub fn has_extra(&self, extra: &str) -> bool {
+        self.extras.contains(&extra)
+    }
+
+    /// Apps the extras picked add to the project, or to its django part.
+    fn django_extra_apps(&self) -> Vec<&'static str> {
+        let mut apps = self.project_type.extra_apps(self);
+        for (_, part_type) in self.project_type.parts() {
+            apps.extend(part_type.extra_apps(self));
+        }
+        apps
+    }
+
+    /// Whether the project is a django one, or has a django part.
+    fn has_django(&self) -> bool {
+        self.project_type == ProjectType::Django
+            || self
+                .project_type
+                .parts()
+                .iter()
+                .any(|(_, part_type)| *part_type == ProjectType::Django)
+    }
+
+    /// Package django's settings, urls and wsgi modules are in, `core` unless another one was picked.
+    pub fn django_package(&self) -> &str {
+        self.django_package.as_deref().unwrap_or("core")
+    }
+
+    /// Fills in a file template's placeholders: `{{name}}` with the project name, `{{crate_name}}` with it as an
+    /// identifier ('-' replaced by '_'), and `{{module_path}}` with the module path.
+    pub fn render(&self, template: &str) -> String {
+        templates::render(
+            template,
+            &[
+                ("name", &self.proj_name),
+                ("crate_name", &self.proj_name.replace('-', "_")),
+                (
+                    "module_path",
+                    self.module_path.as_deref().unwrap_or_default(),
+                ),
+            ],
+        )
+    }
+
+    pub fn run(mut self) -> PEResult {
+        for stage in self.project_type.stages() {
+            Flag::log_if_verbose(format!("stage: {stage:?}").as_str(), self.flags);
+            self.run_stage(*stage)?;
+            signals::check_for_abort()?;
+        }
+        Ok(())
+    }
+
+    fn run_stage(&mut self, stage: Stage) -> PEResult {
+        match stage {
+            Stage::ResolveName => {
+                self.proj_name = self.resolve_project_name()?;
+            }
+            Stage::Configure => {
+                self.variant = self.resolve_variant()?;
+                self.extras = self.resolve_extras()?;
+                self.module_path = self.resolve_module_path()?;
+                self.django_package = self.resolve_django_package()?;
+                self.django_apps = self.resolve_django_apps()?;
+            }
+            Stage::PrepareDir => {
+                Flag::log_if_verbose(
+                    format!("creating {:?} directory", self.proj_name).as_str(),
+                    self.flags,
+                );
+                let (proj_dir, created) = self.create_project_dir()?;
+                self.proj_dir = proj_dir;
+                // a folder reused with --force is the user's own, so it's never offered for removal
+                if created {
+                    signals::track_partial_project(Some(self.proj_dir.clone()));
+                }
+                self.terminal = Terminal::new(self.proj_dir.clone());
+            }
+            Stage::SetUpParts => {
+                for (folder, part_type) in self.project_type.parts() {
+                    self.set_up_part(folder, *part_type)?;
+                }
+            }
+            Stage::Generate => {
+                for step in self.project_type.generate_steps(self) {
+                    self.terminal
+                        .run_cmd(&step.cmd, step.err_msg, step.log_msg, self.flags)?;
+                }
+            }
+            Stage::WriteFiles => {
+                for file in self.project_type.files(self) {
+                    self.write_file(&file)?;
+                }
+                for patch in self.project_type.patches(self) {
+                    self.apply_patch(&patch)?;
+                }
+            }
+            Stage::Migrations => {
+                for step in self.project_type.migration_steps(self) {
+                    self.terminal
+                        .run_cmd(&step.cmd, step.err_msg, step.log_msg, self.flags)?;
+                }
+            }
+            Stage::DetectRoot => {
+                let root = self.detect_root()?;
+                if root != self.terminal.working_dir {
+                    green_log(format!("moved into: {root:#?}").as_str());
+                }
+                self.terminal.working_dir = root;
+            }
+            Stage::Install => {
+                let policy = RetryPolicy::build(&self.project_type, self.flags);
+                for step in self.project_type.install_steps(self) {
+                    self.terminal.run_cmd_with_policy(
+                        &step.cmd,
+                        step.err_msg,
+                        step.log_msg,
+                        &policy,
+                        self.flags,
+                    )?;
+                }
+            }
+            Stage::Testing => {
+                if Flag::wants_testing(self.flags) {
+                    self.set_up_testing()?;
+                }
+            }
+            Stage::Lint => {
+                if Flag::wants_lint(self.flags) {
+                    self.set_up_linting()?;
+                }
+            }
+            Stage::Docker => {
+                if Flag::wants_docker(self.flags) {
+                    self.write_docker_files()?;
+                }
+            }
+            Stage::DevContainer => {
+                if Flag::wants_devcontainer(self.flags) {
+                    for file in devcontainer::files(self) {
+                        self.write_file(&file)?;
+                    }
+                }
+            }
+            Stage::Ci => {
+                if let Some(provider) = Flag::get_ci_provider(self.flags) {
+                    self.write_file(&ci::file(self, provider))?;
+                }
+            }
+            Stage::Readme => {
+                self.write_readme()?;
+            }
+            Stage::License => {
+                if let Some(id) = Flag::get_license(self.flags) {
+                    self.write_license(id)?;
+                }
+            }
+            Stage::InitGit => {
+                self.init_git()?;
+            }
+            Stage::Finalise => {
+                signals::track_partial_project(None);
+                green_log(
+                    format!(
+                        "{:?} project {:?} is ready in {:#?}",
+                        self.project_type, self.proj_name, self.terminal.working_dir
+                    )
+                    .as_str(),
+                );
+                self.open_project();
+            }
+            Stage::Run => {
+                let Some(step) = self.project_type.run_step(self) else {
+                    signals::track_partial_project(None);
+                    return Ok(());
+                };
+                self.terminal.run_dev_server(
+                    &step.cmd,
+                    self.project_type.dev_server_port(),
+                    step.err_msg,
+                    self.flags,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets the project name from `--name`, or prompts for it, and validates it against the project type's naming rules.
+    /// An invalid `--name` is an error, while an invalid prompted name gets a normalised suggestion and is prompted again.
+    fn resolve_project_name(&self) -> PEResult<String> {
+        let project_type = self.project_type;
+        let rules = project_type.naming_rules();
+
+        if let Some(name) = Flag::get_project_name(self.flags) {
+            let name = name.trim();
+            return match rules.check(name) {
+                Ok(_) => Ok(name.to_string()),
+                Err(problems) => Err(ProgramError::new(format!(
+                    "'{name}' is not a valid name for a {project_type:?} project: {}. Try '{}' instead.",
+                    problems.join(", "),
+                    rules.normalise(name)
+                ))),
+            };
+        }
+
+        loop {
+            let name = prompt_input("Enter project name: ")?;
+            let name = name.trim();
+            let Err(problems) = rules.check(name) else {
+                return Ok(name.to_string());
+            };
+
+            let suggestion = rules.normalise(name);
+            red_log(format!("'{name}' can't be used: {}.", problems.join(", ")).as_str());
+            let answer = prompt_input(format!("Use '{suggestion}' instead? (Y/n): ").as_str())?;
+            if answer.trim().is_empty() || answer.trim().eq_ignore_ascii_case("y") {
+                return Ok(suggestion);
+            }
+        }
+    }
+
+    fn resolve_variant(&self) -> PEResult<Option<&'static str>> {
+        let project_type = self.project_type;
+        let variants = project_type.variants();
+        if variants.is_empty() {
+            return Ok(None);
+        }
+
+        match Flag::get_variant(self.flags) {
+            Some(variant) => match variants.iter().find(|v| **v == variant) {
+                Some(variant) => Ok(Some(variant)),
+                None => Err(ProgramError::new(format!(
+                    "'{variant}' is not a variant of a {project_type:?} project, expected one of: {}.",
+                    variants.join(", ")
+                ))),
+            },
+            None if Flag::is_non_interactive(self.flags) => Ok(Some(variants[0])),
+            None => Ok(Some(prompt_choice(
+                format!("Choose a {project_type:?} project variant").as_str(),
+                variants,
+            )?)),
+        }
+    }
+
+    fn resolve_extras(&self) -> PEResult<Vec<&'static str>> {
+        let project_type = self.project_type;
+        let extras = project_type.extras();
+        if extras.is_empty() || self.is_part {
+            return Ok(self.extras.clone());
+        }
+
+        match Flag::get_extras(self.flags) {
+            Some(picked) => picked
+                .iter()
+                .map(|extra| match extras.iter().find(|e| *e == extra) {
+                    Some(extra) => Ok(*extra),
+                    None => Err(ProgramError::new(format!(
+                        "'{extra}' is not an extra of a {project_type:?} project, expected any of: {}.",
+                        extras.join(", ")
+                    ))),
+                })
+                .collect(),
+            None if Flag::is_non_interactive(self.flags) => Ok(vec![]),
+            None => prompt_choices(
+                format!("Pick extras for the {project_type:?} project").as_str(),
+                extras,
+            ),
+        }
+    }
+
+    /// The module path is the config's prefix for the project type joined with the project name, if it has one,
+    /// otherwise it's prompted for (unless `--yes` is passed), defaulting to the project name.
+    fn resolve_module_path(&self) -> PEResult<Option<String>> {
+        let Some(prefix_key) = self.project_type.module_prefix_key() else {
+            return Ok(None);
+        };
+
+        if let Some(prefix) = self.config.get(prefix_key) {
+            let module_path = format!("{}/{}", prefix.trim_end_matches('/'), self.proj_name);
+            return match NamingRules::check_go_module_path(&module_path) {
+                Ok(_) => {
+                    Flag::log_if_verbose(
+                        format!("using module path {module_path} from config").as_str(),
+                        self.flags,
+                    );
+                    Ok(Some(module_path))
+                }
+                Err(problems) => Err(ProgramError::new(format!(
+                    "'{module_path}', from the {prefix_key} config setting, is not a valid module path: {}.",
+                    problems.join(", ")
+                ))),
+            };
+        }
+
+        if Flag::is_non_interactive(self.flags) {
+            return Ok(Some(self.proj_name.clone()));
+        }
+
+        loop {
+            let module_path = prompt_input(
+                format!(
+                    "Enter module path, e.g. github.com/<user>/{} (default: {}): ",
+                    self.proj_name, self.proj_name
+                )
+                .as_str(),
+            )?;
+            let module_path = match module_path.trim() {
+                "" => self.proj_name.as_str(),
+                module_path => module_path,
+            };
+            match NamingRules::check_go_module_path(module_path) {
+                Ok(_) => return Ok(Some(module_path.to_string())),
+                Err(problems) => red_log(
+                    format!("'{module_path}' can't be used: {}.", problems.join(", ")).as_str(),
+                ),
+            }
+        }
+    }
+
+    /// The package django's settings are created in, from `--django-package`, or the `django.package` config setting.
+    /// Named after the project if the flag is passed without a value. Parts are given the package of the project they
+    /// are a part of, as they are named after their folder, and check it against the apps of their own extras.
+    fn resolve_django_package(&self) -> PEResult<Option<String>> {
+        let project_type = self.project_type;
+        if let Some(package) = &self.django_package {
+            if self.django_extra_apps().contains(&package.as_str()) {
+                return Err(ProgramError::new(format!(
+                    "'{package}' can't be used as the django package of the {project_type:?} part, it's the name of an app of the extras picked."
+                )));
+            }
+            return Ok(Some(package.clone()));
+        }
+        if !self.has_django() {
+            return Ok(None);
+        }
+
+        let (package, source) = match Flag::get_django_package(self.flags) {
+            Some(Some(package)) => (package.to_string(), "--django-package"),
+            Some(None) => (
+                NamingRules::PythonIdentifier.normalise(&self.proj_name),
+                "the project name",
+            ),
+            None => match self.config.get("django.package") {
+                Some(package) => (package.to_string(), "the django.package config setting"),
+                None => return Ok(None),
+            },
+        };
+        if let Err(problems) = NamingRules::PythonIdentifier.check(&package) {
+            return Err(ProgramError::new(format!(
+                "'{package}', from {source}, can't be used as the django package: {}.",
+                problems.join(", ")
+            )));
+        }
+        if self.django_extra_apps().contains(&package.as_str()) {
+            return Err(ProgramError::new(format!(
+                "'{package}', from {source}, can't be used as the django package, it's the name of an app of the extras picked."
+            )));
+        }
+
+        Flag::log_if_verbose(
+            format!("using django package {package} from {source}").as_str(),
+            self.flags,
+        );
+        Ok(Some(package))
+    }
+
+    /// The apps passed with `--apps`, for django projects and projects with a django part, checked to be importable and
+    /// not to clash with the settings package or the apps of the extras picked.
+    fn resolve_django_apps(&self) -> PEResult<Vec<String>> {
+        if self.is_part || !self.has_django() {
+            return Ok(self.django_apps.clone());
+        }
+
+        let mut apps: Vec<String> = vec![];
+        for app in Flag::get_apps(self.flags) {
+            if let Err(problems) = NamingRules::PythonIdentifier.check(&app) {
+                return Err(ProgramError::new(format!(
+                    "'{app}', from --apps, is not a valid django app name: {}.",
+                    problems.join(", ")
+                )));
+            }
+            if app == self.django_package() || self.django_extra_apps().contains(&app.as_str()) {
+                return Err(ProgramError::new(format!(
+                    "'{app}', from --apps, clashes with the django package or an app of the extras picked."
+                )));
+            }
+            if !apps.contains(&app) {
+                apps.push(app);
+            }
+        }
+        Ok(apps)
+    }
+
+    /// Sets up `part_type` in `folder`, named after the folder. Naming the project, creating it and running it are left
+    /// to the project it's a part of.
+    fn set_up_part(&self, folder: &str, part_type: ProjectType) -> PEResult {
+        green_log(format!("setting up the {part_type:?} part in {folder}/").as_str());
+
+        let mut part = SetUp::new(part_type, self.flags);
+        part.proj_name = folder.to_string();
+        part.is_part = true;
+        part.extras = self
+            .extras
+            .iter()
+            .filter(|extra| part_type.extras().contains(extra))
+            .copied()
+            .collect();
+        part.django_package = self.django_package.clone();
+        if part_type == ProjectType::Django {
+            part.django_apps = self.django_apps.clone();
+        }
+        part.proj_dir = self.proj_dir.join(folder);
+        if let Err(e) = fs::create_dir_all(&part.proj_dir) {
+            return Err(ProgramError::new(format!(
+                "Failed to create folder {:?} for the {part_type:?} part: {e}",
+                part.proj_dir
+            )));
+        }
+        part.terminal = Terminal::new(part.proj_dir.clone());
+
+        for stage in part_type.stages() {
+            if matches!(
+                stage,
+                Stage::Configure
+                    | Stage::SetUpParts
+                    | Stage::Generate
+                    | Stage::WriteFiles
+                    | Stage::Migrations
+                    | Stage::DetectRoot
+                    | Stage::Install
+            ) {
+                Flag::log_if_verbose(
+                    format!("{part_type:?} part stage: {stage:?}").as_str(),
+                    self.flags,
+                );
+                part.run_stage(*stage)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn init_git(&mut self) -> PEResult {
+        // written even where no repository is initialised, so e.g. `env/` isn't committed to an enclosing one
+        self.write_gitignore()?;
+
+        if Flag::skips_git(self.flags) {
+            Flag::log_if_verbose("skipped git init (--no-git)", self.flags);
+            return Ok(());
+        }
+        if run_seperate_cmd("git --version").is_err() {
+            yellow_log("git is not installed, skipped initialising a repository");
+            return Ok(());
+        }
+
+        let root = self.terminal.working_dir.clone();
+        if let Some(repo) = root.ancestors().find(|dir| dir.join(".git").exists()) {
+            if !Flag::forces_git(self.flags) {
+                yellow_log(
+                    format!("{root:?} is inside the git repository {repo:?}, skipped git init (pass --git to init anyway)")
+                        .as_str(),
+                );
+                // the hooks would go to the enclosing repository, which doesn't have the preset's config at its root
+                if let Some(step) = self.project_type.lint_hooks_step(self) {
+                    yellow_log(
+                        format!("skipped installing the --lint git hooks, run `{}` once {root:?} is its own repository", step.cmd)
+                            .as_str(),
+                    );
+                }
+                return Ok(());
+            }
+        }
+
+        let branch = self.config.get("git.default_branch").unwrap_or("main");
+        // rather than `--initial-branch`, which needs git 2.28
+        self.terminal.run_cmd(
+            &format!("git init --quiet && git symbolic-ref HEAD refs/heads/{branch}"),
+            "Failed to initialise a git repository.",
+            "initialising git repository",
+            self.flags,
+        )?;
+
+        // a missing user.name/user.email shouldn't fail a set-up that is otherwise done
+        let committed = self.terminal.run_cmd(
+            "git add -A && git commit --quiet -m \"Initial commit\"",
+            "Failed to make the initial commit.",
+            "making initial commit",
+            self.flags,
+        );
+        match committed {
+            Err(e) if e.is_aborted() => return Err(e),
+            Err(_) => yellow_log(
+                "could not make the initial commit, check `git config user.name` and `git config user.email` are set",
+            ),
+            Ok(_) => green_log(format!("initialised git repository on branch {branch}").as_str()),
+        }
+
+        // installed after the initial commit, which would otherwise be linted as a whole
+        if let Some(step) = self.project_type.lint_hooks_step(self) {
+            match self
+                .terminal
+                .run_cmd(&step.cmd, step.err_msg, step.log_msg, self.flags)
+            {
+                Err(e) if e.is_aborted() => return Err(e),
+                Err(_) => yellow_log(
+                    format!("{} Run `{}` to install them.", step.err_msg, step.cmd).as_str(),
+                ),
+                Ok(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_readme(&self) -> PEResult {
+        let path = self.terminal.working_dir.join("README.md");
+        let generated = readme::render(self);
+        let readme = match fs::read_to_string(&path) {
+            Ok(upstream) => readme::merge(&generated, &upstream),
+            Err(_) => generated,
+        };
+
+        Flag::log_if_verbose("writing README.md", self.flags);
+        if let Err(e) = fs::write(&path, readme) {
+            return Err(ProgramError::new(format!("Failed to write {path:?}: {e}")));
+        }
+
+        Ok(())
+    }
+
+    fn write_license(&self, id: &str) -> PEResult {
+        let author = self.resolve_author()?;
+        self.write_file(&ProjectFile::new("LICENSE", license::render(id, &author)))?;
+
+        for manifest in self.project_type.license_manifests(self) {
+            let path = self.terminal.working_dir.join(&manifest);
+            let contents = match fs::read_to_string(&path) {
+                Ok(contents) => contents,
+                Err(_) if manifest.ends_with("pyproject.toml") => templates::render(
+                    templates::PYTHON_PYPROJECT_TOML,
+                    &[("name", &self.proj_name)],
+                ),
+                Err(_) => {
+                    yellow_log(format!("found no {manifest} to set the license in").as_str());
+                    continue;
+                }
+            };
+
+            match license::set_manifest_license(&manifest, &contents, id) {
+                Some(contents) => {
+                    Flag::log_if_verbose(
+                        format!("setting the license in {manifest}").as_str(),
+                        self.flags,
+                    );
+                    if let Err(e) = fs::write(&path, contents) {
+                        return Err(ProgramError::new(format!("Failed to write {path:?}: {e}")));
+                    }
+                }
+                None => yellow_log(
+                    format!("could not set the license in {manifest}, set it to \"{id}\" by hand")
+                        .as_str(),
+                ),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The copyright holder named in the LICENSE: the `author` config setting, or the git user's name, or prompted for, falling back to "The <name> authors".
+    fn resolve_author(&self) -> PEResult<String> {
+        if let Some(author) = self.config.get("author") {
+            return Ok(author.to_string());
+        }
+        if let Ok(name) = run_seperate_cmd("git config user.name") {
+            if !name.trim().is_empty() {
+                return Ok(name.trim().to_string());
+            }
+        }
+
+        let default = format!("The {} authors", self.proj_name);
+        if Flag::is_non_interactive(self.flags) {
+            yellow_log(
+                format!("no author config setting nor git user.name, the LICENSE names \"{default}\" as the copyright holder").as_str(),
+            );
+            return Ok(default);
+        }
+        // an empty answer, as read at the end of piped input, takes the default
+        let author = prompt_input(
+            format!("Enter the copyright holder to name in the LICENSE (default: {default}): ")
+                .as_str(),
+        )?;
+        match author.trim() {
+            "" => Ok(default),
+            author => Ok(author.to_string()),
+        }
+    }
+
+    /// Opens the finished project with what `--open`, or otherwise the `open.with` config setting, asks for. Failing to
+    /// is only a warning, as the project is set up either way.
+    fn open_project(&self) {
+        let opener = match Flag::get_opener(self.flags) {
+            Some(opener) => opener,
+            None => match self.config.get("open.with") {
+                Some(name) => match Opener::from_name(name) {
+                    Some(opener) => opener,
+                    None => {
+                        yellow_log(
+                            format!(
+                                "ignored open.with config setting '{name}', expected one of: {}",
+                                VALID_OPENERS.join(", ")
+                            )
+                            .as_str(),
+                        );
+                        return;
+                    }
+                },
+                None => return,
+            },
+        };
+
+        if let Err(e) = opener.open(&self.terminal.working_dir) {
+            yellow_log(e.msg());
+        }
+    }
+
+    /// Writes the project type's .gitignore entries, adding those missing to a .gitignore the generator wrote.
+    fn write_gitignore(&self) -> PEResult {
+        let path = self.terminal.working_dir.join(".gitignore");
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+        let missing: Vec<_> = self
+            .project_type
+            .gitignore_entries(self)
+            .into_iter()
+            .filter(|entry| {
+                !existing
+                    .lines()
+                    .any(|line| line.trim().trim_matches('/') == entry.trim_end_matches('/'))
+            })
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        let gitignore = if existing.is_empty() {
+            format!("{}\n", missing.join("\n"))
+        } else {
+            format!(
+                "{}\n\n# added by plateboiler\n{}\n",
+                existing.trim_end(),
+                missing.join("\n")
+            )
+        };
+        if let Err(e) = fs::write(&path, gitignore) {
+            return Err(ProgramError::new(format!("Failed to write {path:?}: {e}")));
+        }
+
+        Ok(())
+    }
+
+    fn set_up_testing(&mut self) -> PEResult {
+        let project_type = self.project_type;
+        let Some(preset) = project_type.testing_preset(self) else {
+            yellow_log(
+                format!(
+                    "there is no test preset for a {project_type:?} project, skipped --testing"
+                )
+                .as_str(),
+            );
+            return Ok(());
+        };
+        // the example test renders the React template's App
+        if preset == "vitest" && !self.terminal.working_dir.join("src/App.tsx").is_file() {
+            yellow_log("the vite app has no src/App.tsx for vitest's example test to render, skipped --testing");
+            return Ok(());
+        }
+        self.testing = Some(preset);
+
+        let policy = RetryPolicy::build(&project_type, self.flags);
+        for step in project_type.testing_steps(self) {
+            self.terminal.run_cmd_with_policy(
+                &step.cmd,
+                step.err_msg,
+                step.log_msg,
+                &policy,
+                self.flags,
+            )?;
+        }
+        for file in project_type.testing_files(self) {
+            self.write_file(&file)?;
+        }
+        for patch in project_type.testing_patches(self) {
+            self.apply_patch(&patch)?;
+        }
+
+        let Some(step) = project_type
+            .test_step(self)
+            .filter(|_| Flag::runs_tests(self.flags))
+        else {
+            return Ok(());
+        };
+        // the project is set up either way, a failing example test is only reported
+        match self
+            .terminal
+            .run_cmd(&step.cmd, step.err_msg, step.log_msg, self.flags)
+        {
+            Err(e) if e.is_aborted() => return Err(e),
+            Err(_) => {
+                yellow_log(format!("{} Run `{}` to see why.", step.err_msg, step.cmd).as_str())
+            }
+            Ok(_) => green_log("the example tests passed"),
+        }
+
+        Ok(())
+    }
+
+    fn set_up_linting(&mut self) -> PEResult {
+        let project_type = self.project_type;
+        self.write_file(&ProjectFile::new(
+            ".editorconfig",
+            templates::LINT_EDITORCONFIG,
+        ))?;
+        let Some(preset) = project_type.lint_preset(self) else {
+            yellow_log(
+                format!("there is no lint preset for a {project_type:?} project, only wrote an .editorconfig")
+                    .as_str(),
+            );
+            return Ok(());
+        };
+        if let Some(asked) = Flag::get_lint_preset(self.flags).filter(|asked| *asked != preset) {
+            yellow_log(
+                format!(
+                    "--lint={asked} is only for web and next projects, set up {preset} instead"
+                )
+                .as_str(),
+            );
+        }
+
+        let policy = RetryPolicy::build(&project_type, self.flags);
+        for step in project_type.lint_steps(self) {
+            self.terminal.run_cmd_with_policy(
+                &step.cmd,
+                step.err_msg,
+                step.log_msg,
+                &policy,
+                self.flags,
+            )?;
+        }
+        for file in project_type.lint_files(self) {
+            self.write_file(&file)?;
+        }
+        for patch in project_type.lint_patches(self) {
+            self.apply_patch(&patch)?;
+        }
+
+        if let Some(step) = project_type.format_step(self) {
+            // the project is set up either way, it's only left unformatted
+            match self
+                .terminal
+                .run_cmd(&step.cmd, step.err_msg, step.log_msg, self.flags)
+            {
+                Err(e) if e.is_aborted() => return Err(e),
+                Err(_) => yellow_log(
+                    format!("{} Run `{}` to format it.", step.err_msg, step.cmd).as_str(),
+                ),
+                Ok(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_docker_files(&self) -> PEResult {
+        let project_type = self.project_type;
+        let Some(files) = project_type.docker_files(self) else {
+            let variant = self.variant.map_or(String::new(), |v| format!(" ({v})"));
+            yellow_log(
+                format!("skipped --docker, a {project_type:?}{variant} project has nothing to run in a container")
+                    .as_str(),
+            );
+            return Ok(());
+        };
+        if Flag::wants_postgres(self.flags) && !project_type.has_postgres_service() {
+            yellow_log(
+                format!("no Postgres service is added for a {project_type:?} project, only for django and fullstack ones")
+                    .as_str(),
+            );
+        }
+
+        for file in files {
+            self.write_file(&file)?;
+        }
+        for patch in project_type.docker_patches(self) {
+            self.apply_patch(&patch)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_file(&self, file: &ProjectFile) -> PEResult {
+        let path = self.terminal.working_dir.join(&file.path);
+        if path.exists() {
+            yellow_log(format!("kept existing {}", file.path).as_str());
+            return Ok(());
+        }
+
+        Flag::log_if_verbose(format!("writing {}", file.path).as_str(), self.flags);
+        let written = match path.parent() {
+            Some(parent) => {
+                fs::create_dir_all(parent).and_then(|_| fs::write(&path, &file.contents))
+            }
+            None => fs::write(&path, &file.contents),
+        };
+        if let Err(e) = written {
+            return Err(ProgramError::new(format!("Failed to write {path:?}: {e}")));
+        }
+
+        Ok(())
+    }
+
+    fn apply_patch(&self, patch: &FilePatch) -> PEResult {
+        let root = &self.terminal.working_dir;
+        let Some(path) = patch.paths.iter().find(|path| root.join(path).is_file()) else {
+            yellow_log(
+                format!(
+                    "could not find {} to edit, {}",
+                    patch.paths.join(" or "),
+                    patch.hint
+                )
+                .as_str(),
+            );
+            return Ok(());
+        };
+
+        let contents = match fs::read_to_string(root.join(path)) {
+            Ok(contents) => contents,
+            Err(e) => return Err(ProgramError::new(format!("Failed to read {path}: {e}"))),
+        };
+        let Some(patched) = (patch.apply)(&contents) else {
+            yellow_log(format!("could not edit {path}, {}", patch.hint).as_str());
+            return Ok(());
+        };
+        if patched == contents {
+            return Ok(());
+        }
+
+        Flag::log_if_verbose(format!("editing {path}").as_str(), self.flags);
+        if let Err(e) = fs::write(root.join(path), patched) {
+            return Err(ProgramError::new(format!("Failed to write {path}: {e}")));
+        }
+
+        Ok(())
+    }
+
+    /// The generated project's root: the project folder if it holds the project type's root marker file,
+    /// otherwise the only folder directly inside it that does. Anything else is an error, rather than a guess.
+    fn detect_root(&self) -> PEResult<PathBuf> {
+        let marker = self.project_type.root_marker();
+        if self.proj_dir.join(marker).is_file() {
+            return Ok(self.proj_dir.clone());
+        }
+
+        let entries = match fs::read_dir(&self.proj_dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                return Err(ProgramError::new(format!(
+                    "Failed to read project folder {:?} to find the generated project: {e}",
+                    self.proj_dir
+                )))
+            }
+        };
+        let mut candidates: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir() && path.join(marker).is_file())
+            .collect();
+        candidates.sort();
+
+        match candidates.len() {
+            1 => Ok(candidates.remove(0)),
+            0 => Err(ProgramError::new(format!(
+                "Could not find the generated project: no {marker} in {:?} or the folders directly inside it.",
+                self.proj_dir
+            ))),
+            _ => Err(ProgramError::new(format!(
+                "Could not tell which folder is the generated project, found {marker} in: {}.",
+                candidates
+                    .iter()
+                    .map(|path| format!("{path:?}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    /// Creates the project folder `proj_name` inside the target directory (the one set with `in_dir`, or `--dir`, or
+    /// `test_runs/` with `--test`, otherwise the current directory), creating the target directory first if needed.
+    /// An existing project folder is only used if it's empty and `--force` is passed, or emptied with `--force=overwrite`.
+    /// Returns the folder, and whether it was created rather than an existing one used.
+    fn create_project_dir(&self) -> PEResult<(PathBuf, bool)> {
+        let (proj_name, flags) = (&self.proj_name, self.flags);
+        let target_dir = match (&self.target_dir, Flag::get_target_dir(flags)) {
+            (Some(dir), _) => dir.clone(),
+            (None, Some(dir)) => PathBuf::from(dir),
+            (None, None) if Flag::is_test_run(flags) => PathBuf::from("test_runs"),
+            (None, None) => PathBuf::new(),
+        };
+        let target_dir = match env::current_dir() {
+            Ok(current_dir) => current_dir.join(target_dir),
+            Err(e) => {
+                return Err(ProgramError::new(format!(
+                    "Failed to read the current directory: {e}"
+                )))
+            }
+        };
+
+        if let Err(e) = fs::create_dir_all(&target_dir) {
+            return Err(ProgramError::new(format!(
+                "Failed to create target directory {target_dir:?}: {e}"
+            )));
+        }
+
+        let proj_dir = target_dir.join(proj_name);
+        if !proj_dir.exists() {
+            if let Err(e) = fs::DirBuilder::new().create(&proj_dir) {
+                return Err(ProgramError::new(format!(
+                    "Failed to create project folder '{}'. ",
+                    e.kind()
+                )));
+            }
+            return Ok((proj_dir, true));
+        }
+
+        if !proj_dir.is_dir() {
+            return Err(ProgramError::new(format!(
+                "{proj_dir:?} already exists and is not a folder."
+            )));
+        }
+
+        let entries = match fs::read_dir(&proj_dir) {
+            Ok(entries) => entries.filter_map(|entry| entry.ok()).collect::<Vec<_>>(),
+            Err(e) => {
+                return Err(ProgramError::new(format!(
+                    "Failed to read existing project folder {proj_dir:?}: {e}"
+                )))
+            }
+        };
+
+        match Flag::get_force_mode(flags) {
+            ForceMode::Off => Err(ProgramError::new(format!(
+                "Project folder {proj_dir:?} already exists{}. Run again with --force to use it if it's empty, or --force=overwrite to clear it.",
+                Self::list_conflicting_entries(&entries)
+            ))),
+            ForceMode::AllowEmpty if !entries.is_empty() => Err(ProgramError::new(format!(
+                "Project folder {proj_dir:?} is not empty{}. Run again with --force=overwrite to clear it.",
+                Self::list_conflicting_entries(&entries)
+            ))),
+            ForceMode::AllowEmpty => Ok((proj_dir, false)),
+            ForceMode::Overwrite => {
+                if entries.is_empty() {
+                    return Ok((proj_dir, false));
+                }
+
+                let answer = prompt_input(
+                    format!(
+                        "Remove everything in {proj_dir:?}{}? (y/N): ",
+                        Self::list_conflicting_entries(&entries)
+                    )
+                    .as_str(),
+                )?;
+                if !answer.trim().eq_ignore_ascii_case("y") {
+                    return Err(ProgramError::new(format!(
+                        "Did not overwrite existing project folder {proj_dir:?}."
+                    )));
+                }
+
+                for entry in entries {
+                    let path = entry.path();
+                    let removed = if path.is_dir() {
+                        fs::remove_dir_all(&path)
+                    } else {
+                        fs::remove_file(&path)
+                    };
+                    if let Err(e) = removed {
+                        return Err(ProgramError::new(format!(
+                            "Failed to remove {path:?} while overwriting the project folder: {e}"
+                        )));
+                    }
+                }
+
+                Ok((proj_dir, false))
+            }
+        }
+    }
+
+    /// Formats `entries` as ` (conflicting files: a, b, ...)` for error messages, or nothing if there are none.
+    fn list_conflicting_entries(entries: &[fs::DirEntry]) -> String {
+        const MAX_LISTED: usize = 10;
+
+        if entries.is_empty() {
+            return String::new();
+        }
+
+        let mut names: Vec<_> = entries
+            .iter()
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+
+        let mut listed = names
+            .iter()
+            .take(MAX_LISTED)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if names.len() > MAX_LISTED {
+            listed = format!("{listed} and {} more", names.len() - MAX_LISTED);
+        }
+
+        format!(" (conflicting files: {listed})")
+    }
+}
+
+/// The root of the npm, pnpm, yarn or bun workspace `dir` is in, if any: the closest folder up from `dir` with a
+/// pnpm-workspace.yaml, or a package.json listing workspaces.
+pub fn find_workspace_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|dir| {
+            dir.join("pnpm-workspace.yaml").is_file()
+                || fs::read_to_string(dir.join("package.json"))
+                    .is_ok_and(|package_json| package_json.contains("\"workspaces\""))
+        })
+        .map(Path::to_path_buf)
+}
+
+/// The package manager the workspace at `root` is managed with: the one named by its package.json's `packageManager`
+/// field, otherwise the one whose workspace file or lockfile it has. `None` if there is no telling.
+pub fn workspace_package_manager(root: &Path) -> Option<&'static str> {
+    let package_json = fs::read_to_string(root.join("package.json")).unwrap_or_default();
+    if let Some((_, field)) = package_json.split_once("\"packageManager\"") {
+        let field = field.trim_start().trim_start_matches(':').trim_start();
+        let named = VALID_PACKAGE_MANAGERS
+            .iter()
+            .find(|name| field.starts_with(&format!("\"{name}@")));
+        if let Some(name) = named {
+            return Some(name);
+        }
+    }
+
+    [
+        ("pnpm-workspace.yaml", "pnpm"),
+        ("pnpm-lock.yaml", "pnpm"),
+        ("yarn.lock", "yarn"),
+        ("bun.lock", "bun"),
+        ("bun.lockb", "bun"),
+        ("package-lock.json", "npm"),
+    ]
+    .into_iter()
+    .find(|(file, _)| root.join(file).is_file())
+    .map(|(_, name)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn root_is_only_picked_when_unambiguous() {
+        let proj_dir =
+            env::temp_dir().join(format!("plateboiler-detect-root-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&proj_dir);
+        fs::create_dir_all(proj_dir.join("app")).unwrap();
+        fs::write(proj_dir.join("notes.txt"), "").unwrap();
+
+        let mut set_up = SetUp::new(ProjectType::Web, &[]);
+        set_up.proj_dir = proj_dir.clone();
+        assert!(set_up.detect_root().is_err());
+
+        fs::write(proj_dir.join("app/package.json"), "{}").unwrap();
+        assert_eq!(set_up.detect_root().unwrap(), proj_dir.join("app"));
+
+        fs::create_dir_all(proj_dir.join("other")).unwrap();
+        fs::write(proj_dir.join("other/package.json"), "{}").unwrap();
+        assert!(set_up.detect_root().is_err());
+
+        fs::write(proj_dir.join("package.json"), "{}").unwrap();
+        assert_eq!(set_up.detect_root().unwrap(), proj_dir);
+
+        fs::remove_dir_all(&proj_dir).unwrap();
+    }
+
+    #[test]
+    fn workspace_root_is_found_from_inside_it() {
+        let root = env::temp_dir().join(format!("plateboiler-workspace-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("apps/site/src")).unwrap();
+        fs::write(root.join("apps/site/package.json"), "{\"name\": \"site\"}").unwrap();
+        assert_eq!(find_workspace_root(&root.join("apps/site/src")), None);
+
+        fs::write(root.join("package.json"), "{\"workspaces\": [\"apps/*\"]}").unwrap();
+        assert_eq!(
+            find_workspace_root(&root.join("apps/site/src")),
+            Some(root.clone())
+        );
+        assert_eq!(workspace_package_manager(&root), None);
+        fs::write(root.join("yarn.lock"), "").unwrap();
+        assert_eq!(workspace_package_manager(&root), Some("yarn"));
+        fs::write(
+            root.join("package.json"),
+            "{\"workspaces\": [\"apps/*\"],\n  \"packageManager\": \"bun@1.2.0\"}",
+        )
+        .unwrap();
+        assert_eq!(workspace_package_manager(&root), Some("bun"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn gitignore_entries_are_merged_into_existing_file() {
+        let proj_dir =
+            env::temp_dir().join(format!("plateboiler-gitignore-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&proj_dir);
+        fs::create_dir_all(&proj_dir).unwrap();
+        fs::write(proj_dir.join(".gitignore"), "/env\n.idea/\n").unwrap();
+
+        let mut set_up = SetUp::new(ProjectType::Flask, &[]);
+        set_up.terminal = Terminal::new(proj_dir.clone());
+        set_up.write_gitignore().unwrap();
+        let gitignore = fs::read_to_string(proj_dir.join(".gitignore")).unwrap();
+        assert!(gitignore.starts_with("/env\n.idea/\n\n# added by plateboiler\n__pycache__/\n"));
+        assert_eq!(gitignore.matches("env").count(), 2);
+
+        set_up.write_gitignore().unwrap();
+        assert_eq!(
+            fs::read_to_string(proj_dir.join(".gitignore")).unwrap(),
+            gitignore
+        );
+
+        fs::remove_dir_all(&proj_dir).unwrap();
+    }
+}
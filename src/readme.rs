@@ -0,0 +1,145 @@
+//! README generation, see the `Readme` stage.
+//! The README is built from what the project type declares about itself (`ProjectType::stack` and
+//! `ProjectType::dev_commands`) and the tool versions found by the tooling checks.
+
+use crate::{
+    constants::VALID_PROJECT_OPTIONS,
+    data::{Flag, ProjectType},
+    pipeline::{DevCommands, SetUp},
+};
+
+/// README of the project being set up.
+pub fn render(set_up: &SetUp) -> String {
+    let project_type = set_up.project_type;
+    let option = VALID_PROJECT_OPTIONS
+        .iter()
+        .find(|option| option.1 == project_type)
+        .map_or("", |option| option.0);
+
+    let mut readme = format!(
+        "# {}\n\nA {option} project, set up with plateboiler.\n\n## Stack\n\n",
+        set_up.proj_name
+    );
+    for item in project_type.stack(set_up) {
+        readme.push_str(&format!("- {item}\n"));
+    }
+    if let Some(id) = Flag::get_license(set_up.flags) {
+        readme.push_str(&format!("- {id} license, see LICENSE\n"));
+    }
+
+    if set_up.tool_versions.iter().next().is_some() {
+        readme.push_str("\n## Tool versions\n\nFound when the project was set up:\n\n");
+        for (tool, version) in set_up.tool_versions.iter() {
+            readme.push_str(&format!("- {tool} {version}\n"));
+        }
+    }
+
+    readme.push_str(&commands_sections(
+        &project_type.dev_commands(set_up),
+        project_type,
+        "##",
+    ));
+    for (folder, part_type) in project_type.parts() {
+        readme.push_str(&format!("\n## {folder}/\n\nRun these from {folder}/.\n"));
+        readme.push_str(&commands_sections(
+            &part_type.dev_commands(set_up),
+            *part_type,
+            "###",
+        ));
+    }
+
+    if Flag::wants_docker(set_up.flags) && project_type.docker_files(set_up).is_some() {
+        readme.push_str("\n## Docker\n\n```sh\ndocker compose up --build\n```\n");
+        if let Some(port) = project_type.dev_server_port() {
+            readme.push_str(&format!("\nThen open http://localhost:{port}/.\n"));
+        }
+    }
+
+    readme
+}
+
+/// `generated` followed by the README a generator wrote (`upstream`), with `upstream`'s headings moved a level down
+/// so the project keeps a single title.
+pub fn merge(generated: &str, upstream: &str) -> String {
+    let mut in_code_block = false;
+    let upstream: Vec<String> = upstream
+        .trim()
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+            }
+            if !in_code_block && line.starts_with('#') {
+                format!("#{line}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+
+    format!(
+        "{generated}\n## From the generated project\n\n{}\n",
+        upstream.join("\n")
+    )
+}
+
+/// Setup, dev, lint, test and build sections for `commands`, with headings at `level`.
+fn commands_sections(commands: &DevCommands, project_type: ProjectType, level: &str) -> String {
+    let code = |cmds: &[&str]| format!("```sh\n{}\n```\n", cmds.join("\n"));
+    let mut sections = String::new();
+
+    if commands.venv || !commands.setup.is_empty() {
+        let mut setup: Vec<&str> = vec![];
+        if commands.venv {
+            setup.extend(["python -m venv env", ". env/bin/activate"]);
+        }
+        setup.extend(commands.setup.iter().map(String::as_str));
+        sections.push_str(&format!("\n{level} Setup\n\n{}", code(&setup)));
+        if commands.venv {
+            sections.push_str(
+                "\nOn windows, activate the virtual environment with `env\\Scripts\\activate` instead. The commands below \
+                 are run with it activated.\n",
+            );
+        }
+    }
+
+    if let Some(dev) = &commands.dev {
+        let heading = match project_type.dev_server_port() {
+            Some(_) => "Dev server",
+            None => "Running",
+        };
+        sections.push_str(&format!("\n{level} {heading}\n\n{}", code(&[dev])));
+        if let Some(port) = project_type.dev_server_port() {
+            sections.push_str(&format!("\nThen open http://localhost:{port}/.\n"));
+        }
+    }
+    if let Some(lint) = &commands.lint {
+        sections.push_str(&format!("\n{level} Lint\n\n{}", code(&[lint])));
+    }
+    if let Some(test) = &commands.test {
+        sections.push_str(&format!("\n{level} Tests\n\n{}", code(&[test])));
+    }
+    if let Some(build) = &commands.build {
+        sections.push_str(&format!("\n{level} Build\n\n{}", code(&[build])));
+    }
+
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upstream_readme_is_kept_below_with_its_headings_moved_down() {
+        let upstream =
+            "# React + Vite\n\nSome notes.\n\n```sh\n# not a heading\n```\n\n## Expanding\n";
+        let merged = merge("# app\n\nA web project.\n", upstream);
+
+        assert!(!merged.contains("\n# React"));
+        assert!(merged.starts_with("# app\n"));
+        assert!(merged.contains("## From the generated project\n\n## React + Vite\n"));
+        assert!(merged.contains("```sh\n# not a heading\n```"));
+        assert!(merged.contains("\n### Expanding\n"));
+    }
+}
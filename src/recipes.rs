@@ -0,0 +1,2174 @@
+//! What each project type does in the set-up stages it uses, see the `pipeline` module.
+
+use crate::{
+    data::{Flag, PackageManager, ProjectType},
+    lint,
+    pipeline::{DevCommands, FilePatch, ProjectFile, SetUp, Stage, Step},
+    templates, testing, utils,
+};
+
+impl ProjectType {
+    /// The pipeline stages the project type goes through, in order.
+    pub fn stages(&self) -> &'static [Stage] {
+        match self {
+            // django-admin comes with django, so it's installed before generating
+            ProjectType::Django => &[
+                Stage::ResolveName,
+                Stage::Configure,
+                Stage::PrepareDir,
+                Stage::Install,
+                Stage::Generate,
+                Stage::WriteFiles,
+                Stage::Migrations,
+                Stage::DetectRoot,
+                Stage::Testing,
+                Stage::Lint,
+                Stage::Docker,
+                Stage::DevContainer,
+                Stage::Ci,
+                Stage::Readme,
+                Stage::License,
+                Stage::InitGit,
+                Stage::Finalise,
+                Stage::Run,
+            ],
+            ProjectType::Web
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri => &[
+                Stage::ResolveName,
+                Stage::PrepareDir,
+                Stage::Generate,
+                Stage::DetectRoot,
+                Stage::Install,
+                Stage::Testing,
+                Stage::Lint,
+                Stage::Docker,
+                Stage::DevContainer,
+                Stage::Ci,
+                Stage::Readme,
+                Stage::License,
+                Stage::InitGit,
+                Stage::Finalise,
+                Stage::Run,
+            ],
+            ProjectType::Next => &[
+                Stage::ResolveName,
+                Stage::PrepareDir,
+                Stage::Generate,
+                Stage::DetectRoot,
+                Stage::Testing,
+                Stage::Lint,
+                Stage::Docker,
+                Stage::DevContainer,
+                Stage::Ci,
+                Stage::Readme,
+                Stage::License,
+                Stage::InitGit,
+                Stage::Finalise,
+                Stage::Run,
+            ],
+            ProjectType::Rust => &[
+                Stage::ResolveName,
+                Stage::Configure,
+                Stage::PrepareDir,
+                Stage::Generate,
+                Stage::WriteFiles,
+                Stage::DetectRoot,
+                Stage::Testing,
+                Stage::Lint,
+                Stage::Docker,
+                Stage::DevContainer,
+                Stage::Ci,
+                Stage::Readme,
+                Stage::License,
+                Stage::InitGit,
+                Stage::Finalise,
+                Stage::Run,
+            ],
+            // Neither has a generator, the app is written from templates and its requirements.txt installed from.
+            ProjectType::FastApi | ProjectType::Flask => &[
+                Stage::ResolveName,
+                Stage::PrepareDir,
+                Stage::WriteFiles,
+                Stage::Install,
+                Stage::DetectRoot,
+                Stage::Testing,
+                Stage::Lint,
+                Stage::Docker,
+                Stage::DevContainer,
+                Stage::Ci,
+                Stage::Readme,
+                Stage::License,
+                Stage::InitGit,
+                Stage::Finalise,
+                Stage::Run,
+            ],
+            // The app is written from templates, and the framework installed with npm so it's the latest version.
+            ProjectType::NodeApi => &[
+                Stage::ResolveName,
+                Stage::Configure,
+                Stage::PrepareDir,
+                Stage::WriteFiles,
+                Stage::DetectRoot,
+                Stage::Install,
+                Stage::Testing,
+                Stage::Lint,
+                Stage::Docker,
+                Stage::DevContainer,
+                Stage::Ci,
+                Stage::Readme,
+                Stage::License,
+                Stage::InitGit,
+                Stage::Finalise,
+                Stage::Run,
+            ],
+            // The backend and frontend are set up by the django and web flows, then joined up by the files and patches.
+            ProjectType::Fullstack => &[
+                Stage::ResolveName,
+                Stage::Configure,
+                Stage::PrepareDir,
+                Stage::SetUpParts,
+                Stage::WriteFiles,
+                Stage::DetectRoot,
+                Stage::Install,
+                Stage::Testing,
+                Stage::Lint,
+                Stage::Docker,
+                Stage::DevContainer,
+                Stage::Ci,
+                Stage::Readme,
+                Stage::License,
+                Stage::InitGit,
+                Stage::Finalise,
+                Stage::Run,
+            ],
+            // Nothing to run until apps are added to it.
+            ProjectType::Monorepo => &[
+                Stage::ResolveName,
+                Stage::Configure,
+                Stage::PrepareDir,
+                Stage::WriteFiles,
+                Stage::DetectRoot,
+                Stage::Install,
+                Stage::Testing,
+                Stage::Lint,
+                Stage::Docker,
+                Stage::DevContainer,
+                Stage::Ci,
+                Stage::Readme,
+                Stage::License,
+                Stage::InitGit,
+                Stage::Finalise,
+            ],
+            ProjectType::Electron => &[
+                Stage::ResolveName,
+                Stage::PrepareDir,
+                Stage::WriteFiles,
+                Stage::DetectRoot,
+                Stage::Install,
+                Stage::Testing,
+                Stage::Lint,
+                Stage::Docker,
+                Stage::DevContainer,
+                Stage::Ci,
+                Stage::Readme,
+                Stage::License,
+                Stage::InitGit,
+                Stage::Finalise,
+                Stage::Run,
+            ],
+            ProjectType::Go => &[
+                Stage::ResolveName,
+                Stage::Configure,
+                Stage::PrepareDir,
+                Stage::Generate,
+                Stage::WriteFiles,
+                Stage::DetectRoot,
+                Stage::Testing,
+                Stage::Lint,
+                Stage::Docker,
+                Stage::DevContainer,
+                Stage::Ci,
+                Stage::Readme,
+                Stage::License,
+                Stage::InitGit,
+                Stage::Finalise,
+                Stage::Run,
+            ],
+        }
+    }
+
+    /// Layouts the project type can be generated in, the first being the default. Empty if there is only one.
+    pub fn variants(&self) -> &'static [&'static str] {
+        match self {
+            ProjectType::Rust => &["bin", "lib", "workspace"],
+            ProjectType::NodeApi => &["express", "fastify"],
+            ProjectType::Monorepo => &["workspaces", "turborepo", "nx"],
+            ProjectType::Django
+            | ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::FastApi
+            | ProjectType::Flask
+            | ProjectType::Go
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Fullstack => &[],
+        }
+    }
+
+    /// Optional additions to the project that can be picked during set-up. Those of a project with parts include the
+    /// extras of its parts, which are handed the ones they have.
+    pub fn extras(&self) -> &'static [&'static str] {
+        match self {
+            ProjectType::Rust => &["clippy", "rustfmt", "test"],
+            ProjectType::NodeApi => &["typescript"],
+            ProjectType::Django | ProjectType::Fullstack => &["custom-user"],
+            ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::FastApi
+            | ProjectType::Flask
+            | ProjectType::Go
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Monorepo => &[],
+        }
+    }
+
+    /// Apps the extras picked add to a django project, created with startapp before the apps passed with `--apps`.
+    pub fn extra_apps(&self, set_up: &SetUp) -> Vec<&'static str> {
+        match self {
+            ProjectType::Django if set_up.has_extra("custom-user") => vec!["users"],
+            ProjectType::Django
+            | ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::Rust
+            | ProjectType::FastApi
+            | ProjectType::Flask
+            | ProjectType::NodeApi
+            | ProjectType::Go
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Fullstack
+            | ProjectType::Monorepo => vec![],
+        }
+    }
+
+    /// File found at the root of a generated project, used to find it by the `DetectRoot` stage.
+    pub fn root_marker(&self) -> &'static str {
+        match self {
+            ProjectType::Django => "manage.py",
+            ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::NodeApi
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Fullstack
+            | ProjectType::Monorepo => "package.json",
+            ProjectType::Rust => "Cargo.toml",
+            ProjectType::FastApi | ProjectType::Flask => "requirements.txt",
+            ProjectType::Go => "go.mod",
+        }
+    }
+
+    /// Generators are pointed at the project folder itself (`.`), so they don't create a folder of their own.
+    pub fn generate_steps(&self, set_up: &SetUp) -> Vec<Step> {
+        match self {
+            ProjectType::Django => {
+                let mut steps = vec![Step::new(
+                    venv_cmd(&format!(
+                        "django-admin startproject {} .",
+                        set_up.django_package()
+                    )),
+                    "Failed to start a django project.",
+                    "starting a django project",
+                )];
+                let apps = self.extra_apps(set_up).into_iter().map(String::from);
+                for app in apps.chain(set_up.django_apps.iter().cloned()) {
+                    steps.push(Step::new(
+                        venv_cmd(&format!("python manage.py startapp {app}")),
+                        "Failed to create a django app.",
+                        "creating a django app",
+                    ));
+                }
+                steps
+            }
+            // Dependencies are installed by the `Install` stage, with the package manager picked, whenever the create CLI
+            // can be told not to.
+            ProjectType::Web => vec![create_app_step(
+                set_up,
+                "create-vite@latest",
+                ".".to_string(),
+                "--template react-ts --no-interactive",
+                "Failed to create vite app.",
+                "creating vite app",
+            )],
+            ProjectType::Next => vec![create_app_step(
+                set_up,
+                "create-next-app@latest",
+                format!(". --use-{}", package_manager(set_up).name()),
+                "--yes --disable-git",
+                "Failed to create next app.",
+                "creating next app",
+            )],
+            ProjectType::SvelteKit => vec![create_app_step(
+                set_up,
+                "sv@latest",
+                "create . --no-install".to_string(),
+                "--template minimal --types ts --no-add-ons",
+                "Failed to create sveltekit app.",
+                "creating sveltekit app",
+            )],
+            ProjectType::Nuxt => vec![create_app_step(
+                set_up,
+                "nuxi@latest",
+                format!(
+                    "init . --no-install --packageManager {}",
+                    package_manager(set_up).name()
+                ),
+                "--gitInit=false --no-modules",
+                "Failed to create nuxt app.",
+                "creating nuxt app",
+            )],
+            ProjectType::Astro => vec![create_app_step(
+                set_up,
+                "create-astro@latest",
+                ". --no-install".to_string(),
+                "--template basics --yes --no-git --skip-houston",
+                "Failed to create astro app.",
+                "creating astro app",
+            )],
+            ProjectType::Remix => vec![create_app_step(
+                set_up,
+                "create-remix@latest",
+                format!(
+                    ". --no-install --package-manager {}",
+                    package_manager(set_up).name()
+                ),
+                "--yes --no-git-init",
+                "Failed to create remix app.",
+                "creating remix app",
+            )],
+            ProjectType::Tauri => vec![create_app_step(
+                set_up,
+                "create-tauri-app@latest",
+                format!(". --manager {}", package_manager(set_up).name()),
+                "--template vanilla-ts --yes",
+                "Failed to create tauri app.",
+                "creating tauri app",
+            )],
+            // cargo has no generator for workspaces, they are written from templates instead.
+            // Version control is left to the `InitGit` stage, as for every other project type.
+            ProjectType::Rust => match set_up.variant {
+                Some("workspace") => vec![],
+                variant => vec![Step::new(
+                    format!(
+                        "cargo init --{} --vcs none --name {}",
+                        variant.unwrap_or("bin"),
+                        set_up.proj_name
+                    ),
+                    "Failed to create a cargo package.",
+                    "creating cargo package",
+                )],
+            },
+            ProjectType::Go => vec![Step::new(
+                format!(
+                    "go mod init {}",
+                    set_up.module_path.as_deref().unwrap_or(&set_up.proj_name)
+                ),
+                "Failed to create a go module.",
+                "creating go module",
+            )],
+            ProjectType::FastApi
+            | ProjectType::Flask
+            | ProjectType::NodeApi
+            | ProjectType::Electron
+            | ProjectType::Fullstack
+            | ProjectType::Monorepo => vec![],
+        }
+    }
+
+    pub fn files(&self, set_up: &SetUp) -> Vec<ProjectFile> {
+        match self {
+            ProjectType::Rust => {
+                let mut files = vec![];
+                let mut test_dir = String::from("tests");
+                if set_up.variant == Some("workspace") {
+                    let name = &set_up.proj_name;
+                    test_dir = format!("crates/{name}/tests");
+                    files.extend([
+                        ProjectFile::new(
+                            "Cargo.toml",
+                            set_up.render(templates::RUST_WORKSPACE_CARGO_TOML),
+                        ),
+                        ProjectFile::new(
+                            format!("crates/{name}/Cargo.toml"),
+                            set_up.render(templates::RUST_WORKSPACE_BIN_CARGO_TOML),
+                        ),
+                        ProjectFile::new(
+                            format!("crates/{name}/src/main.rs"),
+                            set_up.render(templates::RUST_WORKSPACE_MAIN_RS),
+                        ),
+                        ProjectFile::new(
+                            format!("crates/{name}-core/Cargo.toml"),
+                            set_up.render(templates::RUST_WORKSPACE_LIB_CARGO_TOML),
+                        ),
+                        ProjectFile::new(
+                            format!("crates/{name}-core/src/lib.rs"),
+                            set_up.render(templates::RUST_WORKSPACE_LIB_RS),
+                        ),
+                    ]);
+                }
+
+                if set_up.has_extra("clippy") {
+                    files.push(ProjectFile::new("clippy.toml", templates::RUST_CLIPPY_TOML));
+                }
+                if set_up.has_extra("rustfmt") {
+                    files.push(ProjectFile::new(
+                        "rustfmt.toml",
+                        templates::RUST_RUSTFMT_TOML,
+                    ));
+                }
+                if set_up.has_extra("test") {
+                    let test = match set_up.variant {
+                        Some("lib") => templates::RUST_LIB_TEST,
+                        _ => templates::RUST_BIN_TEST,
+                    };
+                    files.push(ProjectFile::new(
+                        format!("{test_dir}/integration.rs"),
+                        set_up.render(test),
+                    ));
+                }
+
+                files
+            }
+            ProjectType::FastApi => vec![
+                ProjectFile::new("requirements.txt", templates::FASTAPI_REQUIREMENTS_TXT),
+                ProjectFile::new("app/__init__.py", ""),
+                ProjectFile::new(
+                    "app/settings.py",
+                    set_up.render(templates::FASTAPI_SETTINGS_PY),
+                ),
+                ProjectFile::new("app/main.py", templates::FASTAPI_MAIN_PY),
+                ProjectFile::new("pytest.ini", templates::PYTHON_PYTEST_INI),
+                ProjectFile::new(
+                    "tests/test_app.py",
+                    set_up.render(templates::FASTAPI_TEST_PY),
+                ),
+            ],
+            ProjectType::Flask => vec![
+                ProjectFile::new("requirements.txt", templates::FLASK_REQUIREMENTS_TXT),
+                ProjectFile::new("app/__init__.py", templates::FLASK_INIT_PY),
+                ProjectFile::new(
+                    "app/settings.py",
+                    set_up.render(templates::FLASK_SETTINGS_PY),
+                ),
+                ProjectFile::new("wsgi.py", templates::FLASK_WSGI_PY),
+                ProjectFile::new("pytest.ini", templates::PYTHON_PYTEST_INI),
+                ProjectFile::new("tests/test_app.py", set_up.render(templates::FLASK_TEST_PY)),
+            ],
+            ProjectType::NodeApi => {
+                let typescript = set_up.has_extra("typescript");
+                let ext = if typescript { "ts" } else { "js" };
+                let (app, server, test) = match set_up.variant {
+                    Some("fastify") => (
+                        templates::FASTIFY_APP,
+                        templates::FASTIFY_SERVER,
+                        templates::FASTIFY_TEST,
+                    ),
+                    _ if typescript => (
+                        templates::EXPRESS_APP,
+                        templates::EXPRESS_SERVER,
+                        templates::EXPRESS_TEST_TS,
+                    ),
+                    _ => (
+                        templates::EXPRESS_APP,
+                        templates::EXPRESS_SERVER,
+                        templates::EXPRESS_TEST_JS,
+                    ),
+                };
+                let package_json = if typescript {
+                    templates::NODE_API_PACKAGE_JSON_TS
+                } else {
+                    templates::NODE_API_PACKAGE_JSON_JS
+                };
+
+                // The app templates are plain javascript that also type checks as typescript.
+                let mut files = vec![
+                    ProjectFile::new("package.json", set_up.render(package_json)),
+                    ProjectFile::new(format!("src/app.{ext}"), app),
+                    ProjectFile::new(format!("src/server.{ext}"), server),
+                    ProjectFile::new(format!("test/app.test.{ext}"), test),
+                ];
+                if typescript {
+                    files.push(ProjectFile::new(
+                        "tsconfig.json",
+                        templates::NODE_API_TSCONFIG_JSON,
+                    ));
+                }
+
+                files
+            }
+            ProjectType::Fullstack => {
+                // written into package.json, hence the escaped backslashes
+                let venv_python = if cfg!(windows) {
+                    "env\\\\Scripts\\\\python"
+                } else {
+                    "env/bin/python"
+                };
+                vec![ProjectFile::new(
+                    "package.json",
+                    templates::render(
+                        &set_up.render(templates::FULLSTACK_PACKAGE_JSON),
+                        &[
+                            ("package_manager", package_manager(set_up).name()),
+                            ("venv_python", venv_python),
+                        ],
+                    ),
+                )]
+            }
+            ProjectType::Monorepo => {
+                let package_manager = package_manager(set_up);
+                let package_manager_field =
+                    utils::command_version(&[&format!("{} --version", package_manager.name())])
+                        .map(|version| {
+                            format!(
+                                "\n  \"packageManager\": \"{}@{version}\",",
+                                package_manager.name()
+                            )
+                        })
+                        .unwrap_or_default();
+                let package_json = match set_up.variant {
+                    Some("turborepo") => templates::MONOREPO_PACKAGE_JSON_TURBOREPO,
+                    Some("nx") => templates::MONOREPO_PACKAGE_JSON_NX,
+                    _ => templates::MONOREPO_PACKAGE_JSON_WORKSPACES,
+                };
+
+                let mut files = vec![
+                    ProjectFile::new(
+                        "package.json",
+                        templates::render(
+                            &set_up.render(package_json),
+                            &[
+                                ("package_manager_field", &package_manager_field),
+                                ("build_script", &package_manager.workspaces_run_cmd("build")),
+                                ("test_script", &package_manager.workspaces_run_cmd("test")),
+                            ],
+                        ),
+                    ),
+                    ProjectFile::new("apps/.gitkeep", ""),
+                    ProjectFile::new(
+                        "packages/ui/package.json",
+                        set_up.render(templates::MONOREPO_UI_PACKAGE_JSON),
+                    ),
+                    ProjectFile::new(
+                        "packages/ui/index.js",
+                        set_up.render(templates::MONOREPO_UI_INDEX_JS),
+                    ),
+                    ProjectFile::new(
+                        "packages/config/package.json",
+                        set_up.render(templates::MONOREPO_CONFIG_PACKAGE_JSON),
+                    ),
+                    ProjectFile::new(
+                        "packages/config/tsconfig.base.json",
+                        templates::MONOREPO_TSCONFIG_BASE_JSON,
+                    ),
+                ];
+                // pnpm ignores the workspaces in package.json
+                if package_manager == PackageManager::Pnpm {
+                    files.push(ProjectFile::new(
+                        "pnpm-workspace.yaml",
+                        templates::MONOREPO_PNPM_WORKSPACE_YAML,
+                    ));
+                }
+                match set_up.variant {
+                    Some("turborepo") => files.push(ProjectFile::new(
+                        "turbo.json",
+                        templates::MONOREPO_TURBO_JSON,
+                    )),
+                    Some("nx") => {
+                        files.push(ProjectFile::new("nx.json", templates::MONOREPO_NX_JSON))
+                    }
+                    _ => {}
+                }
+
+                files
+            }
+            ProjectType::Electron => vec![
+                ProjectFile::new(
+                    "package.json",
+                    set_up.render(templates::ELECTRON_PACKAGE_JSON),
+                ),
+                ProjectFile::new(
+                    "electron-builder.yml",
+                    set_up.render(templates::ELECTRON_BUILDER_YML),
+                ),
+                ProjectFile::new("src/main/main.js", templates::ELECTRON_MAIN_JS),
+                ProjectFile::new("src/preload/preload.js", templates::ELECTRON_PRELOAD_JS),
+                ProjectFile::new(
+                    "src/renderer/index.html",
+                    set_up.render(templates::ELECTRON_INDEX_HTML),
+                ),
+                ProjectFile::new("src/renderer/renderer.js", templates::ELECTRON_RENDERER_JS),
+            ],
+            ProjectType::Go => {
+                let name = &set_up.proj_name;
+                vec![
+                    ProjectFile::new(
+                        format!("cmd/{name}/main.go"),
+                        set_up.render(templates::GO_MAIN_GO),
+                    ),
+                    ProjectFile::new("internal/app/app.go", set_up.render(templates::GO_APP_GO)),
+                    ProjectFile::new(
+                        "internal/app/app_test.go",
+                        set_up.render(templates::GO_APP_TEST_GO),
+                    ),
+                ]
+            }
+            ProjectType::Django
+            | ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri => vec![],
+        }
+    }
+
+    pub fn install_steps(&self, set_up: &SetUp) -> Vec<Step> {
+        match self {
+            ProjectType::Django => venv_install_steps(
+                "django",
+                "Failed to install django with pip.",
+                "installing django",
+            ),
+            ProjectType::FastApi => venv_install_steps(
+                "-r requirements.txt",
+                "Failed to install fastapi and uvicorn with pip.",
+                "installing fastapi and uvicorn",
+            ),
+            ProjectType::Flask => venv_install_steps(
+                "-r requirements.txt",
+                "Failed to install flask and waitress with pip.",
+                "installing flask and waitress",
+            ),
+            ProjectType::Web
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri => vec![Step::new(
+                package_manager(set_up).install_cmd(),
+                "Failed to install node modules.",
+                "installing node modules...",
+            )],
+            ProjectType::Fullstack => vec![
+                Step::new(
+                    package_manager(set_up).add_cmd("concurrently", true),
+                    "Failed to install concurrently.",
+                    "installing concurrently...",
+                ),
+                Step::new(
+                    format!(
+                        "cd backend && {}",
+                        venv_cmd("pip install django-cors-headers")
+                    ),
+                    "Failed to install django-cors-headers with pip.",
+                    "installing django-cors-headers",
+                ),
+            ],
+            ProjectType::Monorepo => {
+                let package_manager = package_manager(set_up);
+                match set_up.variant {
+                    Some(tool @ ("turborepo" | "nx")) => vec![Step::new(
+                        package_manager.add_to_workspace_root_cmd(if tool == "nx" {
+                            "nx"
+                        } else {
+                            "turbo"
+                        }),
+                        "Failed to install the monorepo tool.",
+                        "installing the monorepo tool...",
+                    )],
+                    _ => vec![Step::new(
+                        package_manager.install_cmd(),
+                        "Failed to install the workspace.",
+                        "installing the workspace...",
+                    )],
+                }
+            }
+            ProjectType::Electron => vec![Step::new(
+                package_manager(set_up).add_cmd("electron electron-builder", true),
+                "Failed to install electron.",
+                "installing electron and electron-builder...",
+            )],
+            ProjectType::NodeApi => {
+                let package_manager = package_manager(set_up);
+                let framework = set_up.variant.unwrap_or("express");
+                let mut steps = vec![Step::new(
+                    package_manager.add_cmd(framework, false),
+                    "Failed to install the framework.",
+                    "installing framework...",
+                )];
+                if set_up.has_extra("typescript") {
+                    // fastify ships its own types
+                    let types = if framework == "express" {
+                        " @types/express"
+                    } else {
+                        ""
+                    };
+                    steps.push(Step::new(
+                        package_manager
+                            .add_cmd(&format!("typescript tsx @types/node{types}"), true),
+                        "Failed to install typescript.",
+                        "installing typescript...",
+                    ));
+                }
+
+                steps
+            }
+            ProjectType::Next | ProjectType::Rust | ProjectType::Go => vec![],
+        }
+    }
+
+    /// Makes the migrations of the models the extras picked add, which the first `migrate` has to find. A custom user
+    /// model can't be swapped in once the database has been migrated without it.
+    pub fn migration_steps(&self, set_up: &SetUp) -> Vec<Step> {
+        match self {
+            ProjectType::Django if set_up.has_extra("custom-user") => vec![Step::new(
+                venv_cmd("python manage.py makemigrations users"),
+                "Failed to make the migrations of the custom user model.",
+                "making the migrations of the custom user model",
+            )],
+            ProjectType::Django
+            | ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::Rust
+            | ProjectType::FastApi
+            | ProjectType::Flask
+            | ProjectType::NodeApi
+            | ProjectType::Go
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Fullstack
+            | ProjectType::Monorepo => vec![],
+        }
+    }
+
+    /// What the `Run` stage runs, the project type's dev command, `None` if there is nothing to run.
+    pub fn run_step(&self, set_up: &SetUp) -> Option<Step> {
+        // the apps added to it are run on their own
+        if *self == ProjectType::Monorepo {
+            return None;
+        }
+        let commands = self.dev_commands(set_up);
+        let dev = commands.dev?;
+        let cmd = if commands.venv { venv_cmd(&dev) } else { dev };
+
+        let (err_msg, log_msg) = match self {
+            ProjectType::Django
+            | ProjectType::FastApi
+            | ProjectType::Flask
+            | ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::NodeApi
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Monorepo => ("Failed to run dev server.", "running dev server..."),
+            ProjectType::Tauri | ProjectType::Electron => {
+                ("Failed to run the app.", "running the app...")
+            }
+            ProjectType::Fullstack => ("Failed to run the dev servers.", "running dev servers..."),
+            ProjectType::Rust | ProjectType::Go => {
+                ("Failed to run the project.", "running the project...")
+            }
+        };
+        Some(Step::new(cmd, err_msg, log_msg))
+    }
+
+    /// The commands to set up, run, test and build the project by hand, as documented in its README.
+    pub fn dev_commands(&self, set_up: &SetUp) -> DevCommands {
+        let package_manager = package_manager(set_up);
+        let install = package_manager.install_cmd();
+        let run = |script: &str| Some(package_manager.run_script_cmd(script));
+
+        let mut commands = match self {
+            ProjectType::Django => {
+                // as the backend of a fullstack project, it also serves the frontend's origin
+                let packages = if set_up.project_type == ProjectType::Fullstack {
+                    "django django-cors-headers"
+                } else {
+                    "django"
+                };
+                DevCommands::in_venv(
+                    format!("pip install {packages}"),
+                    "python manage.py runserver",
+                    "python manage.py test",
+                )
+            }
+            ProjectType::FastApi => DevCommands::in_venv(
+                "pip install -r requirements.txt",
+                "uvicorn app.main:app --reload --port 8000",
+                "pytest",
+            ),
+            ProjectType::Flask => DevCommands::in_venv(
+                "pip install -r requirements.txt",
+                "flask --app app run --debug --port 5000",
+                "pytest",
+            ),
+            ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix => DevCommands {
+                venv: false,
+                setup: vec![install],
+                dev: run("dev"),
+                // the templates used come with eslint, or svelte-check for sveltekit
+                lint: match self {
+                    ProjectType::Web | ProjectType::Next => run("lint"),
+                    ProjectType::SvelteKit => run("check"),
+                    _ => None,
+                },
+                test: None,
+                build: run("build"),
+            },
+            ProjectType::NodeApi => DevCommands {
+                venv: false,
+                setup: vec![install],
+                dev: run("dev"),
+                lint: None,
+                test: run("test"),
+                build: if set_up.has_extra("typescript") {
+                    run("build")
+                } else {
+                    None
+                },
+            },
+            ProjectType::Tauri => DevCommands {
+                venv: false,
+                setup: vec![install],
+                dev: run("tauri dev"),
+                lint: None,
+                test: None,
+                build: run("tauri build"),
+            },
+            ProjectType::Electron => DevCommands {
+                venv: false,
+                setup: vec![install],
+                dev: run("dev"),
+                lint: None,
+                test: None,
+                build: run("dist"),
+            },
+            // the backend and frontend have commands of their own, see `parts`
+            ProjectType::Fullstack => DevCommands {
+                venv: false,
+                setup: vec![install],
+                dev: run("dev"),
+                lint: None,
+                test: None,
+                build: None,
+            },
+            ProjectType::Monorepo => DevCommands {
+                venv: false,
+                setup: vec![install],
+                // the apps added to a plain workspace are run on their own
+                dev: match set_up.variant {
+                    Some("turborepo" | "nx") => run("dev"),
+                    _ => None,
+                },
+                lint: None,
+                test: run("test"),
+                build: run("build"),
+            },
+            ProjectType::Rust => DevCommands {
+                venv: false,
+                setup: vec![],
+                dev: match set_up.variant {
+                    Some("lib") => None,
+                    Some("workspace") => Some(format!("cargo run -p {}", set_up.proj_name)),
+                    _ => Some(String::from("cargo run")),
+                },
+                lint: Some(String::from("cargo clippy --all-targets -- -D warnings")),
+                test: Some(String::from("cargo test")),
+                build: Some(String::from("cargo build --release")),
+            },
+            ProjectType::Go => {
+                let name = &set_up.proj_name;
+                DevCommands {
+                    venv: false,
+                    setup: vec![],
+                    dev: Some(format!("go run ./cmd/{name}")),
+                    lint: Some(String::from("go vet ./...")),
+                    test: Some(String::from("go test ./...")),
+                    build: Some(format!("go build -o bin/{name} ./cmd/{name}")),
+                }
+            }
+        };
+
+        match self.testing(set_up) {
+            Some("vitest") => commands.test = run("test"),
+            Some("playwright") => {
+                // CI runners need the browser's system libraries too
+                commands
+                    .setup
+                    .push(package_manager.bin_cmd("playwright", "install --with-deps chromium"));
+                commands.test = run("test");
+            }
+            Some(_) => {
+                commands
+                    .setup
+                    .push(String::from("pip install pytest pytest-django"));
+                commands.test = Some(String::from("pytest"));
+            }
+            None => {}
+        }
+        // the web presets' lint script is set up by the preset itself
+        if self.lint_preset(set_up) == Some("ruff") {
+            commands
+                .setup
+                .push(String::from("pip install ruff black pre-commit"));
+            commands.lint = Some(String::from("ruff check . && black --check ."));
+        }
+
+        commands
+    }
+
+    /// What the project is made of, as listed in its README.
+    pub fn stack(&self, set_up: &SetUp) -> Vec<String> {
+        let package_manager = package_manager(set_up).name();
+        let created_with = |framework: &str, cli: &str| {
+            vec![
+                format!("{framework}, created with {cli}"),
+                format!("{package_manager} for dependencies"),
+            ]
+        };
+        let venv = String::from("Python, in a virtual environment (env/)");
+
+        let mut stack = match self {
+            ProjectType::Django => vec![
+                venv,
+                format!("Django, with its settings in {}/", set_up.django_package()),
+            ],
+            ProjectType::FastApi => vec![
+                venv,
+                String::from("FastAPI, served by uvicorn"),
+                String::from("pytest"),
+            ],
+            ProjectType::Flask => vec![
+                venv,
+                String::from("Flask, served by waitress in production"),
+                String::from("pytest"),
+            ],
+            ProjectType::Web => created_with("Vite", "create-vite"),
+            ProjectType::Next => created_with("Next.js", "create-next-app"),
+            ProjectType::SvelteKit => created_with("SvelteKit", "sv create"),
+            ProjectType::Nuxt => created_with("Nuxt", "nuxi init"),
+            ProjectType::Astro => created_with("Astro", "create-astro"),
+            ProjectType::Remix => created_with("Remix", "create-remix"),
+            ProjectType::Tauri => {
+                let mut stack = created_with("Tauri", "create-tauri-app");
+                stack.push(String::from("Rust, for the app's backend in src-tauri/"));
+                stack
+            }
+            ProjectType::Electron => vec![
+                String::from("Electron, with its main, preload and renderer code in src/"),
+                String::from("electron-builder for packaging"),
+                format!("{package_manager} for dependencies"),
+            ],
+            ProjectType::NodeApi => {
+                let mut stack = vec![
+                    match set_up.variant {
+                        Some("fastify") => String::from("Node.js with Fastify"),
+                        _ => String::from("Node.js with Express"),
+                    },
+                    String::from("the node test runner"),
+                    format!("{package_manager} for dependencies"),
+                ];
+                if set_up.has_extra("typescript") {
+                    stack.insert(
+                        1,
+                        String::from("TypeScript, run with tsx and built with tsc"),
+                    );
+                }
+                stack
+            }
+            ProjectType::Fullstack => vec![
+                String::from("Django backend in backend/"),
+                String::from("Vite frontend in frontend/, proxying /api and /admin to the backend"),
+                String::from("concurrently, running both dev servers"),
+            ],
+            ProjectType::Monorepo => vec![
+                match set_up.variant {
+                    Some("turborepo") => {
+                        format!("{package_manager} workspaces, run with Turborepo")
+                    }
+                    Some("nx") => format!("{package_manager} workspaces, run with Nx"),
+                    _ => format!("{package_manager} workspaces"),
+                },
+                String::from("apps in apps/, shared packages in packages/"),
+            ],
+            ProjectType::Rust => vec![match set_up.variant {
+                Some("lib") => String::from("Rust library crate, built with cargo"),
+                Some("workspace") => {
+                    String::from("Rust cargo workspace, with its crates in crates/")
+                }
+                _ => String::from("Rust binary crate, built with cargo"),
+            }],
+            ProjectType::Go => vec![format!(
+                "Go module {}",
+                set_up.module_path.as_deref().unwrap_or(&set_up.proj_name)
+            )],
+        };
+        if !set_up.extras.is_empty() {
+            stack.push(format!("extras: {}", set_up.extras.join(", ")));
+        }
+
+        stack
+    }
+
+    /// Config setting holding the prefix of the project's module path, for project types whose package manager
+    /// identifies projects by one. `None` if the project type has no module path.
+    pub fn module_prefix_key(&self) -> Option<&'static str> {
+        match self {
+            ProjectType::Go => Some("go.module_prefix"),
+            ProjectType::Django
+            | ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::Rust
+            | ProjectType::FastApi
+            | ProjectType::Flask
+            | ProjectType::NodeApi
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Fullstack
+            | ProjectType::Monorepo => None,
+        }
+    }
+
+    /// Files the `Docker` stage writes: a multi-stage Dockerfile, a .dockerignore and a compose.yaml, along with what
+    /// they need. `None` if the project type has nothing to run in a container.
+    pub fn docker_files(&self, set_up: &SetUp) -> Option<Vec<ProjectFile>> {
+        let compose = |port: Option<(u16, u16)>| {
+            let ports = port.map_or(String::new(), |(host, container)| {
+                format!("\n    ports:\n      - \"{host}:{container}\"")
+            });
+            ProjectFile::new(
+                "compose.yaml",
+                templates::render(templates::DOCKER_COMPOSE_YAML, &[("ports", &ports)]),
+            )
+        };
+
+        let files = match self {
+            ProjectType::Django => {
+                let mut files = python_docker_files(set_up, "", 8000, &django_docker_cmd(set_up));
+                files.push(django_requirements(set_up, ""));
+                files.push(match Flag::wants_postgres(set_up.flags) {
+                    true => ProjectFile::new(
+                        "compose.yaml",
+                        templates::render(
+                            &set_up.render(templates::DOCKER_COMPOSE_POSTGRES_YAML),
+                            &[("port", "8000")],
+                        ),
+                    ),
+                    false => compose(Some((8000, 8000))),
+                });
+                files
+            }
+            ProjectType::FastApi => {
+                let cmd = r#"["uvicorn", "app.main:app", "--host", "0.0.0.0", "--port", "8000"]"#;
+                let mut files = python_docker_files(set_up, "", 8000, cmd);
+                files.push(compose(Some((8000, 8000))));
+                files
+            }
+            ProjectType::Flask => {
+                let cmd = r#"["waitress-serve", "--port=5000", "wsgi:app"]"#;
+                let mut files = python_docker_files(set_up, "", 5000, cmd);
+                files.push(compose(Some((5000, 5000))));
+                files
+            }
+            ProjectType::Web | ProjectType::Astro => {
+                let mut files = node_static_docker_files(set_up, "", templates::DOCKER_NGINX_CONF);
+                files.push(compose(self.dev_server_port().map(|port| (port, 80))));
+                files
+            }
+            ProjectType::Next => vec![
+                ProjectFile::new(
+                    "Dockerfile",
+                    render_node_dockerfile(
+                        templates::DOCKER_NEXT_DOCKERFILE,
+                        &node_docker_vars(set_up),
+                    ),
+                ),
+                ProjectFile::new(".dockerignore", templates::DOCKER_NODE_DOCKERIGNORE),
+                compose(Some((3000, 3000))),
+            ],
+            ProjectType::Nuxt => {
+                node_server_docker_files(set_up, None, r#"["node", ".output/server/index.mjs"]"#)
+            }
+            // the image is built with adapter-node, leaving the project's own adapter alone
+            ProjectType::SvelteKit => node_server_docker_files(
+                set_up,
+                Some(format!(
+                    "{} && sed -i \"s/adapter-auto/adapter-node/\" svelte.config.js && {}",
+                    package_manager(set_up).add_cmd("@sveltejs/adapter-node", true),
+                    package_manager(set_up).run_script_cmd("build")
+                )),
+                r#"["node", "build"]"#,
+            ),
+            ProjectType::Remix => node_server_docker_files(
+                set_up,
+                Some(package_manager(set_up).run_script_cmd("build")),
+                r#"["node_modules/.bin/remix-serve", "./build/server/index.js"]"#,
+            ),
+            ProjectType::NodeApi => match set_up.has_extra("typescript") {
+                true => node_server_docker_files(
+                    set_up,
+                    Some(package_manager(set_up).run_script_cmd("build")),
+                    r#"["node", "dist/server.js"]"#,
+                ),
+                false => node_server_docker_files(set_up, None, r#"["node", "src/server.js"]"#),
+            },
+            ProjectType::Fullstack => {
+                let mut files =
+                    python_docker_files(set_up, "backend/", 8000, &django_docker_cmd(set_up));
+                files.push(django_requirements(set_up, "backend/"));
+                files.extend(node_static_docker_files(
+                    set_up,
+                    "frontend/",
+                    templates::DOCKER_NGINX_PROXY_CONF,
+                ));
+                files.push(ProjectFile::new(
+                    "compose.yaml",
+                    set_up.render(match Flag::wants_postgres(set_up.flags) {
+                        true => templates::DOCKER_COMPOSE_FULLSTACK_POSTGRES_YAML,
+                        false => templates::DOCKER_COMPOSE_FULLSTACK_YAML,
+                    }),
+                ));
+                files
+            }
+            ProjectType::Rust => {
+                let package_arg = match set_up.variant {
+                    Some("lib") => return None,
+                    Some("workspace") => format!(" -p {}", set_up.proj_name),
+                    _ => String::new(),
+                };
+                vec![
+                    ProjectFile::new(
+                        "Dockerfile",
+                        templates::render(
+                            &set_up.render(templates::DOCKER_RUST_DOCKERFILE),
+                            &[
+                                (
+                                    "rust_version",
+                                    &set_up.tool_versions.pinned_or("rustc", 2, "1"),
+                                ),
+                                ("package_arg", &package_arg),
+                            ],
+                        ),
+                    ),
+                    ProjectFile::new(".dockerignore", templates::DOCKER_RUST_DOCKERIGNORE),
+                    compose(None),
+                ]
+            }
+            ProjectType::Go => vec![
+                ProjectFile::new(
+                    "Dockerfile",
+                    templates::render(
+                        &set_up.render(templates::DOCKER_GO_DOCKERFILE),
+                        &[("go_version", &set_up.tool_versions.pinned_or("go", 2, "1"))],
+                    ),
+                ),
+                ProjectFile::new(".dockerignore", templates::DOCKER_GO_DOCKERIGNORE),
+                compose(None),
+            ],
+            ProjectType::Tauri | ProjectType::Electron | ProjectType::Monorepo => return None,
+        };
+
+        Some(files)
+    }
+
+    /// Edits to generated files the `Docker` stage makes for the Dockerfile and compose.yaml to work.
+    pub fn docker_patches(&self, set_up: &SetUp) -> Vec<FilePatch> {
+        let postgres = Flag::wants_postgres(set_up.flags);
+        let settings = format!("{}/settings.py", set_up.django_package());
+        match self {
+            ProjectType::Next => vec![FilePatch::new(
+                &["next.config.ts", "next.config.mjs", "next.config.js"],
+                output_standalone,
+                "add output: \"standalone\" to the next config by hand",
+            )],
+            ProjectType::Django if postgres => vec![FilePatch::new(
+                &[&settings],
+                use_postgres_from_env,
+                "point DATABASES at the POSTGRES_* environment variables by hand",
+            )],
+            ProjectType::Fullstack if postgres => vec![FilePatch::new(
+                &[&format!("backend/{settings}")],
+                use_postgres_from_env,
+                "point DATABASES at the POSTGRES_* environment variables by hand",
+            )],
+            ProjectType::Django
+            | ProjectType::Web
+            | ProjectType::Rust
+            | ProjectType::FastApi
+            | ProjectType::Flask
+            | ProjectType::NodeApi
+            | ProjectType::Go
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Fullstack
+            | ProjectType::Monorepo => vec![],
+        }
+    }
+
+    /// Whether `--docker=postgres` adds a Postgres service the project is pointed at.
+    pub fn has_postgres_service(&self) -> bool {
+        match self {
+            ProjectType::Django | ProjectType::Fullstack => true,
+            ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::Rust
+            | ProjectType::FastApi
+            | ProjectType::Flask
+            | ProjectType::NodeApi
+            | ProjectType::Go
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Monorepo => false,
+        }
+    }
+
+    /// The test framework preset the `Testing` stage set up for the project, `None` for its parts.
+    fn testing(&self, set_up: &SetUp) -> Option<&'static str> {
+        set_up.testing.filter(|_| set_up.project_type == *self)
+    }
+
+    /// The test framework preset the `Testing` stage sets up: vitest for web projects, playwright for next ones and
+    /// pytest-django for django ones. `None` without `--testing`, for project types without a preset, and for the
+    /// parts of a project.
+    pub fn testing_preset(&self, set_up: &SetUp) -> Option<&'static str> {
+        if !Flag::wants_testing(set_up.flags) || set_up.project_type != *self {
+            return None;
+        }
+
+        match self {
+            ProjectType::Web => Some("vitest"),
+            ProjectType::Next => Some("playwright"),
+            ProjectType::Django => Some("pytest-django"),
+            ProjectType::Rust
+            | ProjectType::FastApi
+            | ProjectType::Flask
+            | ProjectType::NodeApi
+            | ProjectType::Go
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Fullstack
+            | ProjectType::Monorepo => None,
+        }
+    }
+
+    /// Installs the test framework of the project's testing preset.
+    pub fn testing_steps(&self, set_up: &SetUp) -> Vec<Step> {
+        let package_manager = package_manager(set_up);
+        match self.testing(set_up) {
+            Some("vitest") => vec![Step::new(
+                package_manager.add_cmd(
+                    "vitest jsdom @testing-library/react @testing-library/dom @testing-library/jest-dom",
+                    true,
+                ),
+                "Failed to install vitest and testing library.",
+                "installing vitest and testing library...",
+            )],
+            Some("playwright") => vec![
+                Step::new(
+                    package_manager.add_cmd("@playwright/test", true),
+                    "Failed to install playwright.",
+                    "installing playwright...",
+                ),
+                // without --with-deps, which needs root to install the browser's system libraries
+                Step::new(
+                    package_manager.bin_cmd("playwright", "install chromium"),
+                    "Failed to install the browser playwright tests run in.",
+                    "installing chromium for playwright...",
+                ),
+            ],
+            Some(_) => vec![Step::new(
+                venv_cmd("pip install pytest pytest-django"),
+                "Failed to install pytest and pytest-django with pip.",
+                "installing pytest and pytest-django",
+            )],
+            None => vec![],
+        }
+    }
+
+    /// Config and example test files of the project's testing preset, kept if they already exist.
+    pub fn testing_files(&self, set_up: &SetUp) -> Vec<ProjectFile> {
+        match self.testing(set_up) {
+            Some("vitest") => vec![
+                ProjectFile::new("src/test/setup.ts", templates::TESTING_VITEST_SETUP_TS),
+                ProjectFile::new("src/App.test.tsx", templates::TESTING_APP_TEST_TSX),
+            ],
+            Some("playwright") => vec![
+                ProjectFile::new(
+                    "playwright.config.ts",
+                    templates::render(
+                        templates::TESTING_PLAYWRIGHT_CONFIG_TS,
+                        &[("dev", &package_manager(set_up).run_script_cmd("dev"))],
+                    ),
+                ),
+                ProjectFile::new("e2e/home.spec.ts", templates::TESTING_HOME_SPEC_TS),
+            ],
+            // the pytest settings are merged into it by `testing_patches`
+            Some(_) => vec![
+                ProjectFile::new(
+                    "pyproject.toml",
+                    set_up.render(templates::PYTHON_PYPROJECT_TOML),
+                ),
+                ProjectFile::new("tests/test_smoke.py", templates::TESTING_TEST_SMOKE_PY),
+            ],
+            None => vec![],
+        }
+    }
+
+    /// Wires the project's testing preset into the config files the project was generated with.
+    pub fn testing_patches(&self, set_up: &SetUp) -> Vec<FilePatch> {
+        match self.testing(set_up) {
+            Some("vitest") => vec![
+                FilePatch::new(
+                    &["package.json"],
+                    testing::vitest_package_json,
+                    "add \"test\": \"vitest run\" to the scripts in package.json by hand",
+                ),
+                FilePatch::new(
+                    &["vite.config.ts", "vite.config.js"],
+                    testing::vitest_vite_config,
+                    "add vitest's test settings to the vite config by hand",
+                ),
+            ],
+            Some("playwright") => vec![FilePatch::new(
+                &["package.json"],
+                testing::playwright_package_json,
+                "add \"test\": \"playwright test\" to the scripts in package.json by hand",
+            )],
+            Some(_) => vec![FilePatch::new(
+                &["pyproject.toml"],
+                {
+                    let package = set_up.django_package().to_string();
+                    move |pyproject: &str| testing::pytest_pyproject(pyproject, &package)
+                },
+                "add the [tool.pytest.ini_options] settings to pyproject.toml by hand",
+            )],
+            None => vec![],
+        }
+    }
+
+    /// What the `Testing` stage runs with `--testing=run`, the project type's test command.
+    pub fn test_step(&self, set_up: &SetUp) -> Option<Step> {
+        let commands = self.dev_commands(set_up);
+        let test = commands.test?;
+        let cmd = if commands.venv { venv_cmd(&test) } else { test };
+
+        Some(Step::new(
+            cmd,
+            "The example tests failed.",
+            "running the tests...",
+        ))
+    }
+
+    /// The lint preset the `Lint` stage sets up: eslint or biome for web and next projects, ruff for python ones.
+    /// `None` without `--lint`, for project types without a preset, and for the parts of a project.
+    pub fn lint_preset(&self, set_up: &SetUp) -> Option<&'static str> {
+        if !Flag::wants_lint(set_up.flags) || set_up.project_type != *self {
+            return None;
+        }
+
+        match self {
+            ProjectType::Web | ProjectType::Next => match Flag::get_lint_preset(set_up.flags) {
+                Some("biome") => Some("biome"),
+                _ => Some("eslint"),
+            },
+            ProjectType::Django | ProjectType::FastApi | ProjectType::Flask => Some("ruff"),
+            ProjectType::Rust
+            | ProjectType::NodeApi
+            | ProjectType::Go
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Fullstack
+            | ProjectType::Monorepo => None,
+        }
+    }
+
+    /// Installs the tools of the project's lint preset.
+    pub fn lint_steps(&self, set_up: &SetUp) -> Vec<Step> {
+        let package_manager = package_manager(set_up);
+        match self.lint_preset(set_up) {
+            Some("eslint") => vec![Step::new(
+                package_manager.add_cmd("prettier eslint-config-prettier husky lint-staged", true),
+                "Failed to install prettier, husky and lint-staged.",
+                "installing prettier, husky and lint-staged...",
+            )],
+            Some("biome") => vec![Step::new(
+                package_manager.add_cmd("@biomejs/biome husky lint-staged", true),
+                "Failed to install biome, husky and lint-staged.",
+                "installing biome, husky and lint-staged...",
+            )],
+            Some(_) => vec![Step::new(
+                venv_cmd("pip install ruff black pre-commit"),
+                "Failed to install ruff, black and pre-commit with pip.",
+                "installing ruff, black and pre-commit",
+            )],
+            None => vec![],
+        }
+    }
+
+    /// Config files of the project's lint preset, kept if the generator already wrote them.
+    pub fn lint_files(&self, set_up: &SetUp) -> Vec<ProjectFile> {
+        match self.lint_preset(set_up) {
+            Some("eslint") => vec![
+                ProjectFile::new(".prettierrc.json", templates::LINT_PRETTIERRC_JSON),
+                ProjectFile::new(".prettierignore", templates::LINT_PRETTIERIGNORE),
+                ProjectFile::new(".husky/pre-commit", "lint-staged\n"),
+            ],
+            Some("biome") => vec![
+                ProjectFile::new("biome.json", templates::LINT_BIOME_JSON),
+                ProjectFile::new(".husky/pre-commit", "lint-staged\n"),
+            ],
+            // the tool settings are merged into it by `lint_patches`
+            Some(_) => vec![
+                ProjectFile::new(
+                    "pyproject.toml",
+                    set_up.render(templates::PYTHON_PYPROJECT_TOML),
+                ),
+                ProjectFile::new(
+                    ".pre-commit-config.yaml",
+                    templates::LINT_PRE_COMMIT_CONFIG_YAML,
+                ),
+            ],
+            None => vec![],
+        }
+    }
+
+    /// Merges the settings of the project's lint preset into the config files the project was generated with.
+    pub fn lint_patches(&self, set_up: &SetUp) -> Vec<FilePatch> {
+        match self.lint_preset(set_up) {
+            Some("eslint") => vec![
+                FilePatch::new(
+                    &["package.json"],
+                    lint::eslint_package_json,
+                    "add the format and prepare scripts and the lint-staged config to package.json by hand",
+                ),
+                FilePatch::new(
+                    &["eslint.config.js", "eslint.config.mjs", "eslint.config.ts"],
+                    lint::eslint_config_with_prettier,
+                    "add eslint-config-prettier to the eslint config by hand",
+                ),
+            ],
+            Some("biome") => vec![FilePatch::new(
+                &["package.json"],
+                lint::biome_package_json,
+                "add the lint, format and prepare scripts and the lint-staged config to package.json by hand",
+            )],
+            Some(_) => vec![FilePatch::new(
+                &["pyproject.toml"],
+                lint::ruff_pyproject,
+                "add the [tool.ruff] and [tool.black] settings to pyproject.toml by hand",
+            )],
+            None => vec![],
+        }
+    }
+
+    /// Formats the generated project once with the lint preset, so it starts out passing its checks.
+    pub fn format_step(&self, set_up: &SetUp) -> Option<Step> {
+        match self.lint_preset(set_up)? {
+            "eslint" | "biome" => Some(Step::new(
+                package_manager(set_up).run_script_cmd("format"),
+                "Failed to format the project.",
+                "formatting the project",
+            )),
+            _ => Some(Step::new(
+                venv_cmd("ruff check --fix --exit-zero --quiet . && black --quiet ."),
+                "Failed to format the project with ruff and black.",
+                "formatting the project",
+            )),
+        }
+    }
+
+    /// Installs the git hooks of the lint preset, which needs the project to be a repository.
+    pub fn lint_hooks_step(&self, set_up: &SetUp) -> Option<Step> {
+        match self.lint_preset(set_up)? {
+            "eslint" | "biome" => Some(Step::new(
+                package_manager(set_up).run_script_cmd("prepare"),
+                "Failed to install the husky git hooks.",
+                "installing git hooks",
+            )),
+            _ => Some(Step::new(
+                venv_cmd("pre-commit install"),
+                "Failed to install the pre-commit git hooks.",
+                "installing git hooks",
+            )),
+        }
+    }
+
+    /// Runtimes the project is developed with: python, node, go or rust. The first is the one its dev container's
+    /// image comes with, the others are added to it as features.
+    pub fn runtimes(&self) -> &'static [&'static str] {
+        match self {
+            ProjectType::Django | ProjectType::FastApi | ProjectType::Flask => &["python"],
+            ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::NodeApi
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Electron
+            | ProjectType::Monorepo => &["node"],
+            ProjectType::Rust => &["rust"],
+            ProjectType::Go => &["go"],
+            ProjectType::Tauri => &["rust", "node"],
+            ProjectType::Fullstack => &["python", "node"],
+        }
+    }
+
+    /// VS Code extensions recommended in the project's dev container.
+    pub fn editor_extensions(&self, set_up: &SetUp) -> Vec<&'static str> {
+        let web = ["dbaeumer.vscode-eslint", "esbenp.prettier-vscode"];
+        match self {
+            ProjectType::Django => vec!["ms-python.python", "batisteo.vscode-django"],
+            ProjectType::FastApi | ProjectType::Flask => vec!["ms-python.python"],
+            ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::NodeApi
+            | ProjectType::Remix
+            | ProjectType::Electron => web.to_vec(),
+            ProjectType::SvelteKit => [&web[..], &["svelte.svelte-vscode"]].concat(),
+            ProjectType::Nuxt => [&web[..], &["Vue.volar"]].concat(),
+            ProjectType::Astro => [&web[..], &["astro-build.astro-vscode"]].concat(),
+            ProjectType::Monorepo => match set_up.variant {
+                Some("nx") => [&web[..], &["nrwl.angular-console"]].concat(),
+                _ => web.to_vec(),
+            },
+            ProjectType::Rust => vec!["rust-lang.rust-analyzer"],
+            ProjectType::Go => vec!["golang.go"],
+            ProjectType::Tauri => [
+                &["rust-lang.rust-analyzer", "tauri-apps.tauri-vscode"],
+                &web[..],
+            ]
+            .concat(),
+            ProjectType::Fullstack => self
+                .parts()
+                .iter()
+                .flat_map(|(_, part_type)| part_type.editor_extensions(set_up))
+                .collect(),
+        }
+    }
+
+    /// Debian packages the project needs besides its runtimes, installed by its dev container's Dockerfile. A dev
+    /// container without any is built from its image as it is.
+    pub fn system_packages(&self) -> &'static [&'static str] {
+        match self {
+            // the libraries of `TAURI_LINUX_PKG_CONFIG_PACKAGES`, see https://tauri.app/start/prerequisites/#linux
+            ProjectType::Tauri => &[
+                "build-essential",
+                "file",
+                "libwebkit2gtk-4.1-dev",
+                "libxdo-dev",
+                "libssl-dev",
+                "libayatana-appindicator3-dev",
+                "librsvg2-dev",
+            ],
+            ProjectType::Django
+            | ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::Rust
+            | ProjectType::FastApi
+            | ProjectType::Flask
+            | ProjectType::NodeApi
+            | ProjectType::Go
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Electron
+            | ProjectType::Fullstack
+            | ProjectType::Monorepo => &[],
+        }
+    }
+
+    /// Manifests the `License` stage sets the license in, relative to the project root. Python projects don't otherwise
+    /// have a pyproject.toml, so the stage starts one.
+    pub fn license_manifests(&self, set_up: &SetUp) -> Vec<String> {
+        let name = &set_up.proj_name;
+        let manifests = match self {
+            ProjectType::Django | ProjectType::FastApi | ProjectType::Flask => {
+                vec!["pyproject.toml"]
+            }
+            ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::NodeApi
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Electron => vec!["package.json"],
+            ProjectType::Tauri => vec!["package.json", "src-tauri/Cargo.toml"],
+            ProjectType::Rust => match set_up.variant {
+                Some("workspace") => {
+                    return vec![
+                        format!("crates/{name}/Cargo.toml"),
+                        format!("crates/{name}-core/Cargo.toml"),
+                    ]
+                }
+                _ => vec!["Cargo.toml"],
+            },
+            ProjectType::Fullstack => {
+                vec![
+                    "package.json",
+                    "backend/pyproject.toml",
+                    "frontend/package.json",
+                ]
+            }
+            ProjectType::Monorepo => vec![
+                "package.json",
+                "packages/ui/package.json",
+                "packages/config/package.json",
+            ],
+            // go modules have no license field, the LICENSE file is all there is
+            ProjectType::Go => vec![],
+        };
+
+        manifests.into_iter().map(String::from).collect()
+    }
+
+    /// What the `InitGit` stage adds to the project's .gitignore: dependencies, virtual environments, build output and
+    /// caches of the project type.
+    pub fn gitignore_entries(&self, set_up: &SetUp) -> Vec<&'static str> {
+        const PYTHON: [&str; 5] = ["env/", "__pycache__/", "*.pyc", ".pytest_cache/", ".env"];
+        const NODE: [&str; 4] = ["node_modules/", "dist/", ".env", "*.log"];
+
+        let mut entries = vec![];
+        match self {
+            ProjectType::Django => entries.extend(PYTHON.iter().chain(&["db.sqlite3"])),
+            ProjectType::FastApi | ProjectType::Flask => entries.extend(PYTHON),
+            ProjectType::Web | ProjectType::NodeApi => entries.extend(NODE),
+            ProjectType::Next => entries.extend(NODE.iter().chain(&[".next/", "out/"])),
+            ProjectType::SvelteKit => {
+                entries.extend(NODE.iter().chain(&[".svelte-kit/", "build/"]))
+            }
+            ProjectType::Nuxt => entries.extend(NODE.iter().chain(&[".nuxt/", ".output/"])),
+            ProjectType::Astro => entries.extend(NODE.iter().chain(&[".astro/"])),
+            ProjectType::Remix => entries.extend(NODE.iter().chain(&["build/", ".cache/"])),
+            ProjectType::Tauri => entries.extend(NODE.iter().chain(&["src-tauri/target/"])),
+            ProjectType::Electron => entries.extend(NODE.iter().chain(&["release/"])),
+            ProjectType::Monorepo => {
+                entries.extend(NODE);
+                match set_up.variant {
+                    Some("turborepo") => entries.push(".turbo/"),
+                    Some("nx") => entries.extend([".nx/cache/", ".nx/workspace-data/"]),
+                    _ => {}
+                }
+            }
+            // entries without a leading '/' match at any depth, so these cover backend/ and frontend/ too
+            ProjectType::Fullstack => {
+                entries.extend(PYTHON.iter().chain(&NODE).chain(&["db.sqlite3"]))
+            }
+            ProjectType::Rust => entries.push("target/"),
+            ProjectType::Go => entries.extend(["bin/", "*.exe", "*.test", "coverage.out"]),
+        }
+        if self.testing(set_up) == Some("playwright") {
+            entries.extend(["test-results/", "playwright-report/", "playwright/.cache/"]);
+        }
+        let mut unique = vec![];
+        for entry in entries {
+            if !unique.contains(&entry) {
+                unique.push(entry);
+            }
+        }
+
+        unique
+    }
+
+    /// Project types set up inside the project, each in its own folder, by the `SetUpParts` stage.
+    pub fn parts(&self) -> &'static [(&'static str, ProjectType)] {
+        match self {
+            ProjectType::Fullstack => &[
+                ("backend", ProjectType::Django),
+                ("frontend", ProjectType::Web),
+            ],
+            ProjectType::Django
+            | ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::Rust
+            | ProjectType::FastApi
+            | ProjectType::Flask
+            | ProjectType::NodeApi
+            | ProjectType::Go
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Monorepo => &[],
+        }
+    }
+
+    /// Edits to the files generated, made by the `WriteFiles` stage after writing the project type's own files.
+    pub fn patches(&self, set_up: &SetUp) -> Vec<FilePatch> {
+        let settings = format!("{}/settings.py", set_up.django_package());
+        match self {
+            ProjectType::Django => {
+                let mut apps: Vec<String> = self
+                    .extra_apps(set_up)
+                    .into_iter()
+                    .map(String::from)
+                    .collect();
+                apps.extend(set_up.django_apps.iter().cloned());
+                let user_model = set_up.has_extra("custom-user");
+                let mut patches = vec![FilePatch::new(
+                    &[&settings],
+                    move |settings: &str| register_apps(settings, &apps, user_model),
+                    "add the apps to INSTALLED_APPS by hand",
+                )];
+                if user_model {
+                    patches.extend([
+                        FilePatch::new(
+                            &["users/models.py"],
+                            custom_user_model,
+                            "add a User model extending AbstractUser to it by hand",
+                        ),
+                        FilePatch::new(
+                            &["users/admin.py"],
+                            custom_user_admin,
+                            "register the User model with UserAdmin in it by hand",
+                        ),
+                    ]);
+                }
+                patches
+            }
+            ProjectType::Fullstack => vec![
+                FilePatch::new(
+                    &[
+                        "frontend/vite.config.ts",
+                        "frontend/vite.config.js",
+                        "frontend/vite.config.mjs",
+                    ],
+                    proxy_to_django,
+                    "add a server.proxy for /api and /admin to http://127.0.0.1:8000 to it by hand",
+                ),
+                FilePatch::new(
+                    &[&format!("backend/{settings}")],
+                    allow_vite_origin,
+                    "add django-cors-headers to it by hand, see https://pypi.org/project/django-cors-headers",
+                ),
+            ],
+            ProjectType::Web
+            | ProjectType::Next
+            | ProjectType::Rust
+            | ProjectType::FastApi
+            | ProjectType::Flask
+            | ProjectType::NodeApi
+            | ProjectType::Go
+            | ProjectType::SvelteKit
+            | ProjectType::Nuxt
+            | ProjectType::Astro
+            | ProjectType::Remix
+            | ProjectType::Tauri
+            | ProjectType::Electron
+            | ProjectType::Monorepo => vec![],
+        }
+    }
+}
+
+/// Proxies the django routes from the Vite dev server, so the frontend can call the backend on its own origin.
+fn proxy_to_django(vite_config: &str) -> Option<String> {
+    if vite_config.contains("proxy:") {
+        return Some(vite_config.to_string());
+    }
+
+    let (before, after) = vite_config.split_once("defineConfig({")?;
+    Some(format!(
+        "{before}defineConfig({{\n  server: {{\n    proxy: {{\n      '/api': 'http://127.0.0.1:8000',\n      '/admin': 'http://127.0.0.1:8000',\n    }},\n  }},{after}"
+    ))
+}
+
+/// Lets the Vite dev server's origin call django directly, for requests that don't go through its proxy.
+fn allow_vite_origin(settings: &str) -> Option<String> {
+    if settings.contains("corsheaders") {
+        return Some(settings.to_string());
+    }
+
+    let (before, after) = settings.split_once("INSTALLED_APPS = [")?;
+    let settings = format!("{before}INSTALLED_APPS = [\n    \"corsheaders\",{after}");
+    let (before, after) = settings.split_once("MIDDLEWARE = [")?;
+    Some(format!(
+        "{before}MIDDLEWARE = [\n    \"corsheaders.middleware.CorsMiddleware\",{after}\nCORS_ALLOWED_ORIGINS = [\"http://localhost:5173\", \"http://127.0.0.1:5173\"]\n"
+    ))
+}
+
+/// Adds `apps` to the end of INSTALLED_APPS, and points AUTH_USER_MODEL at the users app's model with `user_model`.
+fn register_apps(settings: &str, apps: &[String], user_model: bool) -> Option<String> {
+    let (before, after) = settings.split_once("INSTALLED_APPS = [")?;
+    let (installed, after) = after.split_once("\n]")?;
+    let mut installed = installed.to_string();
+    for app in apps {
+        if !installed.contains(&format!("'{app}'")) && !installed.contains(&format!("\"{app}\"")) {
+            installed.push_str(&format!("\n    \"{app}\","));
+        }
+    }
+
+    let mut settings = format!("{before}INSTALLED_APPS = [{installed}\n]{after}");
+    if user_model && !settings.contains("AUTH_USER_MODEL") {
+        settings = format!(
+            "{}\n\nAUTH_USER_MODEL = \"users.User\"\n",
+            settings.trim_end()
+        );
+    }
+    Some(settings)
+}
+
+/// The users app's model, in place of the empty models.py startapp writes.
+fn custom_user_model(models: &str) -> Option<String> {
+    if models.contains("class User(") {
+        return Some(models.to_string());
+    }
+    models
+        .contains("# Create your models here.")
+        .then(|| templates::DJANGO_USERS_MODELS_PY.to_string())
+}
+
+/// Registers the users app's model with django's admin for users, in place of the empty admin.py startapp writes.
+fn custom_user_admin(admin: &str) -> Option<String> {
+    if admin.contains("UserAdmin") {
+        return Some(admin.to_string());
+    }
+    admin
+        .contains("# Register your models here.")
+        .then(|| templates::DJANGO_USERS_ADMIN_PY.to_string())
+}
+
+/// Makes next build a standalone server, which its Dockerfile runs.
+fn output_standalone(next_config: &str) -> Option<String> {
+    if next_config.contains("output:") {
+        return Some(next_config.to_string());
+    }
+
+    let (before, after) = next_config.split_once("nextConfig")?;
+    let (declaration, after) = after.split_once('{')?;
+    Some(format!(
+        "{before}nextConfig{declaration}{{\n  output: \"standalone\",{after}"
+    ))
+}
+
+/// Points django at the Postgres service of the compose.yaml, when it's run by it.
+fn use_postgres_from_env(settings: &str) -> Option<String> {
+    if settings.contains("POSTGRES_HOST") {
+        return Some(settings.to_string());
+    }
+    if !settings.contains("DATABASES = {") {
+        return None;
+    }
+
+    Some(format!(
+        "{}\n{}",
+        settings.trim_end(),
+        templates::DJANGO_POSTGRES_SETTINGS_PY
+    ))
+}
+
+/// Migrates the database, which is empty in a fresh container, before serving the app.
+fn django_docker_cmd(set_up: &SetUp) -> String {
+    format!(
+        r#"["sh", "-c", "python manage.py migrate && gunicorn {}.wsgi:application --bind 0.0.0.0:8000"]"#,
+        set_up.django_package()
+    )
+}
+
+/// Dockerfile and .dockerignore of a python app in `dir` (relative to the project root, ending with '/').
+fn python_docker_files(set_up: &SetUp, dir: &str, port: u16, cmd: &str) -> Vec<ProjectFile> {
+    vec![
+        ProjectFile::new(
+            format!("{dir}Dockerfile"),
+            templates::render(
+                templates::DOCKER_PYTHON_DOCKERFILE,
+                &[
+                    (
+                        "python_version",
+                        &set_up.tool_versions.pinned_or("python", 2, "3"),
+                    ),
+                    ("port", &port.to_string()),
+                    ("cmd", cmd),
+                ],
+            ),
+        ),
+        ProjectFile::new(
+            format!("{dir}.dockerignore"),
+            templates::DOCKER_PYTHON_DOCKERIGNORE,
+        ),
+    ]
+}
+
+/// requirements.txt of a django project in `dir`, which is otherwise set up without one, for its Dockerfile.
+fn django_requirements(set_up: &SetUp, dir: &str) -> ProjectFile {
+    let mut requirements = vec!["django", "gunicorn"];
+    if set_up.project_type == ProjectType::Fullstack {
+        requirements.push("django-cors-headers");
+    }
+    if Flag::wants_postgres(set_up.flags) {
+        requirements.push("psycopg[binary]");
+    }
+
+    ProjectFile::new(
+        format!("{dir}requirements.txt"),
+        format!("{}\n", requirements.join("\n")),
+    )
+}
+
+/// Placeholders shared by the node Dockerfiles: the node version, and how the package manager is set up and run.
+fn node_docker_vars(set_up: &SetUp) -> Vec<(&'static str, String)> {
+    let package_manager = package_manager(set_up);
+    let package_manager_setup = package_manager
+        .setup_cmd()
+        .map_or(String::new(), |cmd| format!("RUN {cmd}\n"));
+
+    vec![
+        (
+            "node_version",
+            set_up.tool_versions.pinned_or("node", 1, "lts"),
+        ),
+        ("package_manager_setup", package_manager_setup),
+        ("install", package_manager.install_cmd()),
+        ("build", package_manager.run_script_cmd("build")),
+    ]
+}
+
+fn render_node_dockerfile(template: &str, vars: &[(&'static str, String)]) -> String {
+    let vars: Vec<(&str, &str)> = vars
+        .iter()
+        .map(|(key, value)| (*key, value.as_str()))
+        .collect();
+    templates::render(template, &vars)
+}
+
+/// Dockerfile, .dockerignore and nginx config of a static site in `dir` (relative to the project root, ending with '/').
+fn node_static_docker_files(set_up: &SetUp, dir: &str, nginx_conf: &str) -> Vec<ProjectFile> {
+    vec![
+        ProjectFile::new(
+            format!("{dir}Dockerfile"),
+            render_node_dockerfile(
+                templates::DOCKER_NODE_STATIC_DOCKERFILE,
+                &node_docker_vars(set_up),
+            ),
+        ),
+        ProjectFile::new(
+            format!("{dir}.dockerignore"),
+            templates::DOCKER_NODE_DOCKERIGNORE,
+        ),
+        ProjectFile::new(format!("{dir}nginx.conf"), nginx_conf),
+    ]
+}
+
+/// Docker files of a node server, built with `build` (if it needs building) and started with `cmd`.
+fn node_server_docker_files(set_up: &SetUp, build: Option<String>, cmd: &str) -> Vec<ProjectFile> {
+    let port = set_up.project_type.dev_server_port().unwrap_or(3000);
+    let mut vars = node_docker_vars(set_up);
+    vars.extend([
+        (
+            "build_step",
+            build.map_or(String::new(), |build| format!("RUN {build}\n")),
+        ),
+        ("port", port.to_string()),
+        ("cmd", cmd.to_string()),
+    ]);
+
+    vec![
+        ProjectFile::new(
+            "Dockerfile",
+            render_node_dockerfile(templates::DOCKER_NODE_SERVER_DOCKERFILE, &vars),
+        ),
+        ProjectFile::new(".dockerignore", templates::DOCKER_NODE_DOCKERIGNORE),
+        ProjectFile::new(
+            "compose.yaml",
+            templates::render(
+                templates::DOCKER_COMPOSE_YAML,
+                &[("ports", &format!("\n    ports:\n      - \"{port}:{port}\""))],
+            ),
+        ),
+    ]
+}
+
+fn package_manager(set_up: &SetUp) -> PackageManager {
+    Flag::get_package_manager(set_up.flags)
+}
+
+/// Runs a create CLI (`package`) with the package manager picked, adding `non_interactive_args` with `--yes` so it
+/// takes its defaults instead of prompting.
+fn create_app_step(
+    set_up: &SetUp,
+    package: &str,
+    args: String,
+    non_interactive_args: &str,
+    err_msg: &'static str,
+    log_msg: &'static str,
+) -> Step {
+    let package_manager = package_manager(set_up);
+    if !Flag::is_non_interactive(set_up.flags) {
+        return Step::new(package_manager.exec_cmd(package, &args), err_msg, log_msg);
+    }
+
+    // npx also asks before downloading the package, the other package managers don't
+    let package = match package_manager {
+        PackageManager::Npm => format!("--yes {package}"),
+        _ => package.to_string(),
+    };
+    Step::new(
+        package_manager.exec_cmd(&package, &format!("{args} {non_interactive_args}")),
+        err_msg,
+        log_msg,
+    )
+}
+
+impl DevCommands {
+    /// Commands run in the project's python virtual environment, which has nothing to build.
+    fn in_venv(setup: impl Into<String>, dev: &str, test: &str) -> Self {
+        Self {
+            venv: true,
+            setup: vec![setup.into()],
+            dev: Some(dev.to_string()),
+            lint: None,
+            test: Some(test.to_string()),
+            build: None,
+        }
+    }
+}
+
+/// Creates the `env` virtual environment in the project folder, and pip installs `packages` into it.
+fn venv_install_steps(packages: &str, err_msg: &'static str, log_msg: &'static str) -> Vec<Step> {
+    vec![
+        Step::new(
+            "python -m venv env",
+            "Failed to create virtual env.",
+            "setting up virtual environment",
+        ),
+        Step::new(
+            venv_cmd(&format!("pip install {packages}")),
+            err_msg,
+            log_msg,
+        ),
+    ]
+}
+
+/// Prefixes `cmd` with activating the `env` virtual environment created in the project folder.
+fn venv_cmd(cmd: &str) -> String {
+    let activate_cmd = if cfg!(windows) {
+        "env\\Scripts\\activate.bat"
+    } else {
+        ". env/bin/activate"
+    };
+    format!("{activate_cmd} && {cmd}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fullstack_patches_apply_once() {
+        let vite_config = "export default defineConfig({\n  plugins: [react()],\n})\n";
+        let patched = proxy_to_django(vite_config).unwrap();
+        assert!(patched.contains("'/api': 'http://127.0.0.1:8000'"));
+        assert!(patched.ends_with("  },\n  plugins: [react()],\n})\n"));
+        assert_eq!(proxy_to_django(&patched).unwrap(), patched);
+        assert!(proxy_to_django("module.exports = {}").is_none());
+
+        let settings = "INSTALLED_APPS = [\n    'django.contrib.admin',\n]\n\nMIDDLEWARE = [\n    'django.middleware.security.SecurityMiddleware',\n]\n";
+        let patched = allow_vite_origin(settings).unwrap();
+        assert!(patched
+            .contains("INSTALLED_APPS = [\n    \"corsheaders\",\n    'django.contrib.admin',"));
+        assert!(patched.contains(
+            "MIDDLEWARE = [\n    \"corsheaders.middleware.CorsMiddleware\",\n    'django.middleware"
+        ));
+        assert!(patched.contains("CORS_ALLOWED_ORIGINS"));
+        assert_eq!(allow_vite_origin(&patched).unwrap(), patched);
+    }
+
+    #[test]
+    fn django_apps_are_registered_once() {
+        let settings = "INSTALLED_APPS = [\n    'django.contrib.admin',\n    'django.contrib.staticfiles',\n]\n\nMIDDLEWARE = [\n    'x',\n]\n";
+        let apps = [String::from("users"), String::from("shop")];
+        let patched = register_apps(settings, &apps, true).unwrap();
+        assert!(patched.starts_with("INSTALLED_APPS = [\n    'django.contrib.admin',\n    'django.contrib.staticfiles',\n    \"users\",\n    \"shop\",\n]\n\nMIDDLEWARE"));
+        assert!(patched.ends_with("]\n\nAUTH_USER_MODEL = \"users.User\"\n"));
+        assert_eq!(register_apps(&patched, &apps, true).unwrap(), patched);
+        assert!(register_apps("DEBUG = True\n", &apps, false).is_none());
+
+        let models = "from django.db import models\n\n# Create your models here.\n";
+        let patched = custom_user_model(models).unwrap();
+        assert!(patched.contains("class User(AbstractUser):"));
+        assert_eq!(custom_user_model(&patched).unwrap(), patched);
+    }
+
+    #[test]
+    fn docker_patches_apply_once() {
+        let next_config = "const nextConfig: NextConfig = {\n  /* config options here */\n};\n";
+        let patched = output_standalone(next_config).unwrap();
+        assert_eq!(
+            patched,
+            "const nextConfig: NextConfig = {\n  output: \"standalone\",\n  /* config options here */\n};\n"
+        );
+        assert_eq!(output_standalone(&patched).unwrap(), patched);
+
+        let settings = "DATABASES = {\n    'default': {}\n}\n\n";
+        let patched = use_postgres_from_env(settings).unwrap();
+        assert!(patched.starts_with("DATABASES = {\n    'default': {}\n}\n\n# "));
+        assert_eq!(use_postgres_from_env(&patched).unwrap(), patched);
+        assert!(use_postgres_from_env("DEBUG = True\n").is_none());
+    }
+}
@@ -0,0 +1,95 @@
+//! SIGINT/SIGTERM handling.
+//! Signals are recorded, and forwarded to the running child command if there is one, so `Terminal::run_cmd` can wait
+//! for the child to exit and report the abort. Set-up checks for a recorded signal between stages, before starting
+//! commands and while prompting (`check_for_abort`), so an abort always goes through `clean_up_after_abort`.
+
+use crate::{data::ProgramError, utils::PEResult};
+
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Mutex,
+    },
+};
+
+/// Last signal received and not yet checked for, 0 if none.
+static RECEIVED_SIGNAL: AtomicI32 = AtomicI32::new(0);
+
+/// Where to forward signals to: the running child's pid, or its negated process group id. 0 if no child is running.
+static FORWARD_TARGET: AtomicI32 = AtomicI32::new(0);
+
+/// Project folder created by the current set-up, which is only partially built until the set-up is complete.
+static PARTIAL_PROJECT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+#[cfg(unix)]
+pub fn install_handlers() {
+    // SAFETY: `handle_signal` only touches atomics and calls async-signal-safe functions. Without SA_RESTART, a read
+    // of a prompt's answer is interrupted by the signal instead of blocking until enter is pressed.
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = handle_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        libc::sigemptyset(&mut action.sa_mask);
+        libc::sigaction(libc::SIGINT, &action, std::ptr::null_mut());
+        libc::sigaction(libc::SIGTERM, &action, std::ptr::null_mut());
+    }
+}
+
+// On windows Ctrl-C is delivered to every process attached to the console, children included, so the default
+// behaviour already stops them.
+#[cfg(not(unix))]
+pub fn install_handlers() {}
+
+#[cfg(unix)]
+extern "C" fn handle_signal(signal: libc::c_int) {
+    RECEIVED_SIGNAL.store(signal, Ordering::SeqCst);
+    let target = FORWARD_TARGET.load(Ordering::SeqCst);
+    if target == 0 {
+        return;
+    }
+
+    // SAFETY: `kill` is async-signal-safe, `target` is a pid (or negated pgid) we spawned.
+    unsafe {
+        libc::kill(target, signal);
+    }
+}
+
+/// Registers the child that signals should be forwarded to until `clear_running_child` is called.
+/// `own_group` should be true if the child was spawned as the leader of its own process group.
+pub fn set_running_child(pid: u32, own_group: bool) {
+    let pid = pid as i32;
+    FORWARD_TARGET.store(if own_group { -pid } else { pid }, Ordering::SeqCst);
+}
+
+pub fn clear_running_child() {
+    FORWARD_TARGET.store(0, Ordering::SeqCst);
+}
+
+/// Returns the signal received since the last check, if any, and resets it.
+pub fn take_received_signal() -> Option<i32> {
+    match RECEIVED_SIGNAL.swap(0, Ordering::SeqCst) {
+        0 => None,
+        signal => Some(signal),
+    }
+}
+
+/// Fails as aborted if a signal was received since the last check, for set-up to stop before its next step.
+pub fn check_for_abort() -> PEResult {
+    match take_received_signal() {
+        Some(signal) => Err(ProgramError::aborted(format!(
+            "Interrupted by signal {signal}."
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Sets (or with `None`, clears) the project folder to offer removing if set-up is aborted.
+pub fn track_partial_project(dir: Option<PathBuf>) {
+    if let Ok(mut partial_project) = PARTIAL_PROJECT.lock() {
+        *partial_project = dir;
+    }
+}
+
+pub fn partial_project() -> Option<PathBuf> {
+    PARTIAL_PROJECT.lock().ok().and_then(|dir| dir.clone())
+}
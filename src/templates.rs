@@ -0,0 +1,128 @@
+//! File templates written into generated projects, bundled into the binary so set-up works offline.
+//! Placeholders are written `{{key}}`, and filled in by `render`.
+
+pub const RUST_CLIPPY_TOML: &str = include_str!("templates/rust/clippy.toml");
+pub const RUST_RUSTFMT_TOML: &str = include_str!("templates/rust/rustfmt.toml");
+pub const RUST_BIN_TEST: &str = include_str!("templates/rust/bin_test.rs");
+pub const RUST_LIB_TEST: &str = include_str!("templates/rust/lib_test.rs");
+pub const RUST_WORKSPACE_CARGO_TOML: &str = include_str!("templates/rust/workspace/Cargo.toml");
+pub const RUST_WORKSPACE_BIN_CARGO_TOML: &str =
+    include_str!("templates/rust/workspace/bin_Cargo.toml");
+pub const RUST_WORKSPACE_MAIN_RS: &str = include_str!("templates/rust/workspace/main.rs");
+pub const RUST_WORKSPACE_LIB_CARGO_TOML: &str =
+    include_str!("templates/rust/workspace/lib_Cargo.toml");
+pub const RUST_WORKSPACE_LIB_RS: &str = include_str!("templates/rust/workspace/lib.rs");
+
+pub const PYTHON_PYTEST_INI: &str = include_str!("templates/python/pytest.ini");
+pub const PYTHON_PYPROJECT_TOML: &str = include_str!("templates/python/pyproject.toml");
+pub const DJANGO_USERS_MODELS_PY: &str = include_str!("templates/python/django/users_models.py");
+pub const DJANGO_USERS_ADMIN_PY: &str = include_str!("templates/python/django/users_admin.py");
+pub const FASTAPI_REQUIREMENTS_TXT: &str =
+    include_str!("templates/python/fastapi/requirements.txt");
+pub const FASTAPI_SETTINGS_PY: &str = include_str!("templates/python/fastapi/settings.py");
+pub const FASTAPI_MAIN_PY: &str = include_str!("templates/python/fastapi/main.py");
+pub const FASTAPI_TEST_PY: &str = include_str!("templates/python/fastapi/test_app.py");
+pub const FLASK_REQUIREMENTS_TXT: &str = include_str!("templates/python/flask/requirements.txt");
+pub const FLASK_SETTINGS_PY: &str = include_str!("templates/python/flask/settings.py");
+pub const FLASK_INIT_PY: &str = include_str!("templates/python/flask/__init__.py");
+pub const FLASK_WSGI_PY: &str = include_str!("templates/python/flask/wsgi.py");
+pub const FLASK_TEST_PY: &str = include_str!("templates/python/flask/test_app.py");
+
+pub const NODE_API_PACKAGE_JSON_JS: &str = include_str!("templates/node_api/package_js.json");
+pub const NODE_API_PACKAGE_JSON_TS: &str = include_str!("templates/node_api/package_ts.json");
+pub const NODE_API_TSCONFIG_JSON: &str = include_str!("templates/node_api/tsconfig.json");
+pub const EXPRESS_APP: &str = include_str!("templates/node_api/express/app.js");
+pub const EXPRESS_SERVER: &str = include_str!("templates/node_api/express/server.js");
+pub const EXPRESS_TEST_JS: &str = include_str!("templates/node_api/express/app.test.js");
+pub const EXPRESS_TEST_TS: &str = include_str!("templates/node_api/express/app.test.ts");
+pub const FASTIFY_APP: &str = include_str!("templates/node_api/fastify/app.js");
+pub const FASTIFY_SERVER: &str = include_str!("templates/node_api/fastify/server.js");
+pub const FASTIFY_TEST: &str = include_str!("templates/node_api/fastify/app.test.js");
+
+pub const GO_MAIN_GO: &str = include_str!("templates/go/main.go");
+pub const GO_APP_GO: &str = include_str!("templates/go/app.go");
+pub const GO_APP_TEST_GO: &str = include_str!("templates/go/app_test.go");
+
+pub const ELECTRON_PACKAGE_JSON: &str = include_str!("templates/electron/package.json");
+pub const ELECTRON_BUILDER_YML: &str = include_str!("templates/electron/electron-builder.yml");
+pub const ELECTRON_MAIN_JS: &str = include_str!("templates/electron/main.js");
+pub const ELECTRON_PRELOAD_JS: &str = include_str!("templates/electron/preload.js");
+pub const ELECTRON_INDEX_HTML: &str = include_str!("templates/electron/index.html");
+pub const ELECTRON_RENDERER_JS: &str = include_str!("templates/electron/renderer.js");
+
+pub const FULLSTACK_PACKAGE_JSON: &str = include_str!("templates/fullstack/package.json");
+
+pub const MONOREPO_PACKAGE_JSON_WORKSPACES: &str =
+    include_str!("templates/monorepo/package_workspaces.json");
+pub const MONOREPO_PACKAGE_JSON_TURBOREPO: &str =
+    include_str!("templates/monorepo/package_turborepo.json");
+pub const MONOREPO_PACKAGE_JSON_NX: &str = include_str!("templates/monorepo/package_nx.json");
+pub const MONOREPO_PNPM_WORKSPACE_YAML: &str =
+    include_str!("templates/monorepo/pnpm-workspace.yaml");
+pub const MONOREPO_TURBO_JSON: &str = include_str!("templates/monorepo/turbo.json");
+pub const MONOREPO_NX_JSON: &str = include_str!("templates/monorepo/nx.json");
+pub const MONOREPO_UI_PACKAGE_JSON: &str =
+    include_str!("templates/monorepo/packages/ui_package.json");
+pub const MONOREPO_UI_INDEX_JS: &str = include_str!("templates/monorepo/packages/ui_index.js");
+pub const MONOREPO_CONFIG_PACKAGE_JSON: &str =
+    include_str!("templates/monorepo/packages/config_package.json");
+pub const MONOREPO_TSCONFIG_BASE_JSON: &str =
+    include_str!("templates/monorepo/packages/tsconfig.base.json");
+
+pub const DOCKER_PYTHON_DOCKERFILE: &str = include_str!("templates/docker/python.Dockerfile");
+pub const DOCKER_PYTHON_DOCKERIGNORE: &str = include_str!("templates/docker/python.dockerignore");
+pub const DOCKER_NODE_STATIC_DOCKERFILE: &str =
+    include_str!("templates/docker/node_static.Dockerfile");
+pub const DOCKER_NODE_SERVER_DOCKERFILE: &str =
+    include_str!("templates/docker/node_server.Dockerfile");
+pub const DOCKER_NEXT_DOCKERFILE: &str = include_str!("templates/docker/next.Dockerfile");
+pub const DOCKER_NODE_DOCKERIGNORE: &str = include_str!("templates/docker/node.dockerignore");
+pub const DOCKER_NGINX_CONF: &str = include_str!("templates/docker/nginx.conf");
+pub const DOCKER_NGINX_PROXY_CONF: &str = include_str!("templates/docker/nginx_proxy.conf");
+pub const DOCKER_RUST_DOCKERFILE: &str = include_str!("templates/docker/rust.Dockerfile");
+pub const DOCKER_RUST_DOCKERIGNORE: &str = include_str!("templates/docker/rust.dockerignore");
+pub const DOCKER_GO_DOCKERFILE: &str = include_str!("templates/docker/go.Dockerfile");
+pub const DOCKER_GO_DOCKERIGNORE: &str = include_str!("templates/docker/go.dockerignore");
+pub const DOCKER_COMPOSE_YAML: &str = include_str!("templates/docker/compose.yaml");
+pub const DOCKER_COMPOSE_POSTGRES_YAML: &str =
+    include_str!("templates/docker/compose_postgres.yaml");
+pub const DOCKER_COMPOSE_FULLSTACK_YAML: &str =
+    include_str!("templates/docker/compose_fullstack.yaml");
+pub const DOCKER_COMPOSE_FULLSTACK_POSTGRES_YAML: &str =
+    include_str!("templates/docker/compose_fullstack_postgres.yaml");
+pub const DJANGO_POSTGRES_SETTINGS_PY: &str = include_str!("templates/docker/postgres_settings.py");
+
+pub const LINT_EDITORCONFIG: &str = include_str!("templates/lint/editorconfig");
+pub const LINT_PRETTIERRC_JSON: &str = include_str!("templates/lint/prettierrc.json");
+pub const LINT_PRETTIERIGNORE: &str = include_str!("templates/lint/prettierignore");
+pub const LINT_BIOME_JSON: &str = include_str!("templates/lint/biome.json");
+pub const LINT_PRE_COMMIT_CONFIG_YAML: &str = include_str!("templates/lint/pre-commit-config.yaml");
+pub const LINT_PYPROJECT_TOOLS_TOML: &str = include_str!("templates/lint/pyproject_tools.toml");
+
+pub const TESTING_VITEST_SETUP_TS: &str = include_str!("templates/testing/vitest_setup.ts");
+pub const TESTING_APP_TEST_TSX: &str = include_str!("templates/testing/App.test.tsx");
+pub const TESTING_PLAYWRIGHT_CONFIG_TS: &str =
+    include_str!("templates/testing/playwright.config.ts");
+pub const TESTING_HOME_SPEC_TS: &str = include_str!("templates/testing/home.spec.ts");
+pub const TESTING_TEST_SMOKE_PY: &str = include_str!("templates/testing/test_smoke.py");
+pub const TESTING_PYPROJECT_PYTEST_TOML: &str =
+    include_str!("templates/testing/pyproject_pytest.toml");
+
+pub const LICENSE_MIT: &str = include_str!("templates/licenses/MIT.txt");
+pub const LICENSE_APACHE_2_0: &str = include_str!("templates/licenses/Apache-2.0.txt");
+pub const LICENSE_GPL_2_0: &str = include_str!("templates/licenses/GPL-2.0.txt");
+pub const LICENSE_GPL_3_0: &str = include_str!("templates/licenses/GPL-3.0.txt");
+pub const LICENSE_LGPL_3_0: &str = include_str!("templates/licenses/LGPL-3.0.txt");
+pub const LICENSE_BSD_2_CLAUSE: &str = include_str!("templates/licenses/BSD-2-Clause.txt");
+pub const LICENSE_BSD_3_CLAUSE: &str = include_str!("templates/licenses/BSD-3-Clause.txt");
+pub const LICENSE_ISC: &str = include_str!("templates/licenses/ISC.txt");
+pub const LICENSE_MPL_2_0: &str = include_str!("templates/licenses/MPL-2.0.txt");
+pub const LICENSE_UNLICENSE: &str = include_str!("templates/licenses/Unlicense.txt");
+
+/// Fills in the `{{key}}` placeholders of `template` with the values in `vars`.
+pub fn render(template: &str, vars: &[(&str, &str)]) -> String {
+    vars.iter()
+        .fold(template.to_string(), |rendered, (key, value)| {
+            rendered.replace(&format!("{{{{{key}}}}}"), value)
+        })
+}
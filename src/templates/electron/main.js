@@ -0,0 +1,33 @@
+const path = require("node:path");
+const { app, BrowserWindow } = require("electron");
+
+function createWindow() {
+  const window = new BrowserWindow({
+    width: 1024,
+    height: 768,
+    webPreferences: {
+      preload: path.join(__dirname, "..", "preload", "preload.js"),
+      contextIsolation: true,
+      nodeIntegration: false,
+    },
+  });
+
+  window.loadFile(path.join(__dirname, "..", "renderer", "index.html"));
+}
+
+app.whenReady().then(() => {
+  createWindow();
+
+  // on macOS apps stay open without windows, and reopen one when clicked in the dock
+  app.on("activate", () => {
+    if (BrowserWindow.getAllWindows().length === 0) {
+      createWindow();
+    }
+  });
+});
+
+app.on("window-all-closed", () => {
+  if (process.platform !== "darwin") {
+    app.quit();
+  }
+});
@@ -0,0 +1,8 @@
+// Runs before the renderer loads, and is the only place the renderer gets anything from node/electron through.
+const { contextBridge } = require("electron");
+
+contextBridge.exposeInMainWorld("versions", {
+  node: process.versions.node,
+  chrome: process.versions.chrome,
+  electron: process.versions.electron,
+});
@@ -0,0 +1,4 @@
+const { node, chrome, electron } = window.versions;
+
+document.getElementById("versions").textContent =
+  `Electron ${electron}, Chrome ${chrome}, Node.js ${node}`;
@@ -0,0 +1,9 @@
+// Package app holds the code of {{name}} that is not meant to be imported by other modules.
+package app
+
+import "fmt"
+
+// Greeting returns the message printed on start up.
+func Greeting(name string) string {
+	return fmt.Sprintf("Hello from %s!", name)
+}
@@ -0,0 +1,12 @@
+package app
+
+import "testing"
+
+func TestGreeting(t *testing.T) {
+	got := Greeting("{{name}}")
+	want := "Hello from {{name}}!"
+
+	if got != want {
+		t.Errorf("Greeting() = %q, want %q", got, want)
+	}
+}
@@ -0,0 +1,11 @@
+package main
+
+import (
+	"fmt"
+
+	"{{module_path}}/internal/app"
+)
+
+func main() {
+	fmt.Println(app.Greeting("{{name}}"))
+}
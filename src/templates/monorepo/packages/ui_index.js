@@ -0,0 +1,12 @@
+// Shared UI for the apps in apps/, import it with `import { theme } from "@{{name}}/ui"` after adding
+// "@{{name}}/ui" to an app's dependencies.
+
+export const theme = {
+  colors: {
+    primary: "#2563eb",
+    text: "#111827",
+    background: "#ffffff",
+  },
+  radius: "0.5rem",
+  font: "system-ui, sans-serif",
+};
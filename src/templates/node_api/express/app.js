@@ -0,0 +1,12 @@
+import express from "express";
+
+export function buildApp() {
+  const app = express();
+  app.use(express.json());
+
+  app.get("/health", (_req, res) => {
+    res.json({ status: "ok" });
+  });
+
+  return app;
+}
@@ -0,0 +1,20 @@
+import assert from "node:assert/strict";
+import { once } from "node:events";
+import { after, test } from "node:test";
+
+import { buildApp } from "../src/app.js";
+
+const server = buildApp().listen(0);
+after(() => server.close());
+
+test("GET /health answers ok", async () => {
+  if (!server.listening) {
+    await once(server, "listening");
+  }
+  const { port } = server.address();
+
+  const response = await fetch(`http://127.0.0.1:${port}/health`);
+
+  assert.equal(response.status, 200);
+  assert.deepEqual(await response.json(), { status: "ok" });
+});
@@ -0,0 +1,7 @@
+import { buildApp } from "./app.js";
+
+const port = Number(process.env.PORT ?? 3000);
+
+buildApp().listen(port, () => {
+  console.log(`Listening on http://localhost:${port}`);
+});
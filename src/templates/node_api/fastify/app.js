@@ -0,0 +1,9 @@
+import Fastify from "fastify";
+
+export function buildApp(logger = true) {
+  const app = Fastify({ logger });
+
+  app.get("/health", async () => ({ status: "ok" }));
+
+  return app;
+}
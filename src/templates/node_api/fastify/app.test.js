@@ -0,0 +1,13 @@
+import assert from "node:assert/strict";
+import { test } from "node:test";
+
+import { buildApp } from "../src/app.js";
+
+test("GET /health answers ok", async () => {
+  const app = buildApp(false);
+
+  const response = await app.inject({ method: "GET", url: "/health" });
+
+  assert.equal(response.statusCode, 200);
+  assert.deepEqual(response.json(), { status: "ok" });
+});
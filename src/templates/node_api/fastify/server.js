@@ -0,0 +1,11 @@
+import { buildApp } from "./app.js";
+
+const port = Number(process.env.PORT ?? 3000);
+// fastify only listens on localhost unless told otherwise, which a container needs (HOST=0.0.0.0)
+const host = process.env.HOST ?? "localhost";
+const app = buildApp();
+
+app.listen({ port, host }).catch((error) => {
+  app.log.error(error);
+  process.exit(1);
+});
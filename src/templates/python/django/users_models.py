@@ -0,0 +1,6 @@
+from django.contrib.auth.models import AbstractUser
+
+
+# Set as AUTH_USER_MODEL, so fields can be added to users later without swapping models.
+class User(AbstractUser):
+    pass
@@ -0,0 +1,15 @@
+from fastapi import FastAPI
+
+from app.settings import settings
+
+app = FastAPI(title=settings.app_name, debug=settings.debug)
+
+
+@app.get("/")
+def index() -> dict:
+    return {"message": f"Hello from {settings.app_name}!"}
+
+
+@app.get("/health")
+def health() -> dict:
+    return {"status": "ok"}
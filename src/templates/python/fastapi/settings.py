@@ -0,0 +1,17 @@
+"""Settings read from environment variables, so each environment can override them without code changes."""
+
+import os
+from dataclasses import dataclass, field
+
+
+def _env_flag(name: str, default: bool) -> bool:
+    return os.environ.get(name, str(default)).lower() in ("1", "true", "yes")
+
+
+@dataclass(frozen=True)
+class Settings:
+    app_name: str = field(default_factory=lambda: os.environ.get("APP_NAME", "{{name}}"))
+    debug: bool = field(default_factory=lambda: _env_flag("DEBUG", True))
+
+
+settings = Settings()
@@ -0,0 +1,19 @@
+from fastapi.testclient import TestClient
+
+from app.main import app
+
+client = TestClient(app)
+
+
+def test_index():
+    response = client.get("/")
+
+    assert response.status_code == 200
+    assert response.json() == {"message": "Hello from {{name}}!"}
+
+
+def test_health():
+    response = client.get("/health")
+
+    assert response.status_code == 200
+    assert response.json() == {"status": "ok"}
@@ -0,0 +1,21 @@
+import os
+from typing import Optional
+
+from flask import Flask
+
+from app.settings import configs
+
+
+def create_app(env: Optional[str] = None) -> Flask:
+    app = Flask(__name__)
+    app.config.from_object(configs[env or os.environ.get("APP_ENV", "development")])
+
+    @app.get("/")
+    def index():
+        return {"message": f"Hello from {app.config['APP_NAME']}!"}
+
+    @app.get("/health")
+    def health():
+        return {"status": "ok"}
+
+    return app
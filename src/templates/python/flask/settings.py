@@ -0,0 +1,29 @@
+"""Settings per environment, picked by name in `create_app` or with the APP_ENV environment variable."""
+
+import os
+
+
+class Config:
+    APP_NAME = os.environ.get("APP_NAME", "{{name}}")
+    SECRET_KEY = os.environ.get("SECRET_KEY", "change-me")
+    DEBUG = False
+    TESTING = False
+
+
+class DevelopmentConfig(Config):
+    DEBUG = True
+
+
+class TestingConfig(Config):
+    TESTING = True
+
+
+class ProductionConfig(Config):
+    SECRET_KEY = os.environ.get("SECRET_KEY")
+
+
+configs = {
+    "development": DevelopmentConfig,
+    "testing": TestingConfig,
+    "production": ProductionConfig,
+}
@@ -0,0 +1,22 @@
+import pytest
+
+from app import create_app
+
+
+@pytest.fixture
+def client():
+    return create_app("testing").test_client()
+
+
+def test_index(client):
+    response = client.get("/")
+
+    assert response.status_code == 200
+    assert response.get_json() == {"message": "Hello from {{name}}!"}
+
+
+def test_health(client):
+    response = client.get("/health")
+
+    assert response.status_code == 200
+    assert response.get_json() == {"status": "ok"}
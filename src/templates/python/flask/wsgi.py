@@ -0,0 +1,5 @@
+"""Entry point for WSGI servers, e.g. `waitress-serve wsgi:app`."""
+
+from app import create_app
+
+app = create_app()
@@ -0,0 +1,11 @@
+use std::process::Command;
+
+#[test]
+fn runs_successfully() {
+    let output = Command::new(env!("CARGO_BIN_EXE_{{name}}"))
+        .output()
+        .expect("the binary should run");
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "Hello, world!\n");
+}
@@ -0,0 +1,4 @@
+#[test]
+fn adds_numbers() {
+    assert_eq!({{crate_name}}::add(2, 2), 4);
+}
@@ -0,0 +1,14 @@
+/// The greeting printed by the `{{name}}` binary.
+pub fn greeting() -> &'static str {
+    "Hello, world!"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn greets_the_world() {
+        assert_eq!(greeting(), "Hello, world!");
+    }
+}
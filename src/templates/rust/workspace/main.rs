@@ -0,0 +1,3 @@
+fn main() {
+    println!("{}", {{crate_name}}_core::greeting());
+}
@@ -0,0 +1,12 @@
+import { render, screen } from "@testing-library/react";
+import { describe, expect, it } from "vitest";
+import App from "./App";
+
+describe("App", () => {
+  it("renders the Vite and React logos", () => {
+    render(<App />);
+
+    expect(screen.getByAltText("Vite logo")).toBeInTheDocument();
+    expect(screen.getByAltText("React logo")).toBeInTheDocument();
+  });
+});
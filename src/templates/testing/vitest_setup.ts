@@ -0,0 +1,2 @@
+// adds the DOM matchers of jest-dom, such as toBeInTheDocument, to vitest's expect
+import "@testing-library/jest-dom/vitest";
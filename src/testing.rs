@@ -0,0 +1,56 @@
+//! Edits wiring the `--testing` framework into the package.json, vite config and pyproject the generators wrote.
+
+use crate::{manifest, templates};
+
+/// package.json with vitest run by the test script, once, as CI and the `Testing` stage need.
+pub fn vitest_package_json(package_json: &str) -> Option<String> {
+    manifest::set_scripts(
+        package_json,
+        &[("test", "vitest run"), ("test:watch", "vitest")],
+        &[],
+    )
+}
+
+/// A vite config with vitest's settings: a browser-like DOM, and the jest-dom matchers set up before each test file.
+pub fn vitest_vite_config(vite_config: &str) -> Option<String> {
+    if vite_config.contains("test:") {
+        return Some(vite_config.to_string());
+    }
+
+    let (before, after) = vite_config.split_once("defineConfig({")?;
+    // the reference types the `test` key for tsc
+    Some(format!(
+        "/// <reference types=\"vitest/config\" />\n{before}defineConfig({{\n  test: {{\n    environment: \"jsdom\",\n    setupFiles: \"./src/test/setup.ts\",\n  }},{after}"
+    ))
+}
+
+pub fn playwright_package_json(package_json: &str) -> Option<String> {
+    manifest::set_scripts(package_json, &[("test", "playwright test")], &[])
+}
+
+/// pyproject.toml with pytest pointed at the settings in the django `package`, and finding django's tests.py files.
+pub fn pytest_pyproject(pyproject: &str, package: &str) -> Option<String> {
+    Some(manifest::add_toml_tables(
+        pyproject,
+        &templates::render(
+            templates::TESTING_PYPROJECT_PYTEST_TOML,
+            &[("package", package)],
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vitest_is_wired_into_the_vite_config_once() {
+        let vite_config = "import { defineConfig } from 'vite'\n\nexport default defineConfig({\n  plugins: [react()],\n})\n";
+        let patched = vitest_vite_config(vite_config).unwrap();
+
+        assert!(patched.starts_with("/// <reference types=\"vitest/config\" />\nimport"));
+        assert!(patched.contains("defineConfig({\n  test: {\n    environment: \"jsdom\",\n    setupFiles: \"./src/test/setup.ts\",\n  },\n  plugins: [react()],"));
+        assert_eq!(vitest_vite_config(&patched).unwrap(), patched);
+        assert!(vitest_vite_config("module.exports = {}").is_none());
+    }
+}
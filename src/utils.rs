@@ -1,44 +1,82 @@
 use colored::*;
 
 use std::{
+    collections::VecDeque,
     env::consts,
-    io::{self, Write},
-    process::Command,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream},
+    process::{Child, Command},
+    thread::{self, JoinHandle},
+    time::Duration,
 };
 
-use crate::data::ProgramError;
+use crate::{data::ProgramError, signals};
 
 pub type PEResult<T = ()> = Result<T, ProgramError>;
 
 pub fn check_if_any_command_passes(cmds: &[&str]) -> Result<(), ()> {
     let mut check_result = Err(());
     for cmd in cmds {
-        match run_seperate_cmd(cmd) {
-            Ok(_) => check_result = Ok(()),
-            Err(_) => (),
+        if run_seperate_cmd(cmd).is_ok() {
+            check_result = Ok(())
         }
     }
     check_result
 }
 
-pub fn run_seperate_cmd(cmd: &str) -> PEResult {
-    if consts::OS == "linux" {
-        let output = Command::new("sh").arg("-c").arg(cmd).output();
-        if let Err(e) = output {
-            return Err(ProgramError::new(format!("Error running `{cmd}`: {e}")));
-        }
-        Ok(())
+/// Runs `cmd` with its output captured, returning its stdout, or its stderr if stdout was empty.
+/// Fails if the command could not be run or did not exit successfully.
+pub fn run_seperate_cmd(cmd: &str) -> PEResult<String> {
+    let output = if consts::OS == "linux" {
+        Command::new("sh").arg("-c").arg(cmd).output()
     } else if consts::OS == "windows" {
-        let output = Command::new("cmd").arg("/C").arg(cmd).output();
-        if let Err(e) = output {
-            return Err(ProgramError::new(format!("Error running `{cmd}`: {e}")));
+        Command::new("cmd").arg("/C").arg(cmd).output()
+    } else {
+        return Err(ProgramError::new("OS not supported by CLI".to_string()));
+    };
+
+    match output {
+        Err(e) => Err(ProgramError::new(format!("Error running `{cmd}`: {e}"))),
+        Ok(output) if !output.status.success() => Err(ProgramError::new(format!(
+            "`{cmd}` exited with {}",
+            output.status
+        ))),
+        Ok(output) if output.stdout.is_empty() => {
+            Ok(String::from_utf8_lossy(&output.stderr).into_owned())
         }
-        Ok(())
+        Ok(output) => Ok(String::from_utf8_lossy(&output.stdout).into_owned()),
+    }
+}
+
+/// Whether `program` can be found on the PATH (or at all, if it is a path).
+pub fn command_exists(program: &str) -> bool {
+    if consts::OS == "windows" {
+        run_seperate_cmd(&format!("where {program}")).is_ok()
     } else {
-        Err(ProgramError::new(format!("OS not supported by CLI")))
+        run_seperate_cmd(&format!("command -v {program}")).is_ok()
     }
 }
 
+/// First line of output of the first of `cmds` that passes, e.g. the version a `<tool> --version` prints.
+pub fn command_version(cmds: &[&str]) -> Option<String> {
+    cmds.iter().find_map(|cmd| {
+        let output = run_seperate_cmd(cmd).ok()?;
+        let first_line = output.lines().next()?.trim();
+        (!first_line.is_empty()).then(|| first_line.to_string())
+    })
+}
+
+/// The version number in a line printed by a version command, e.g. "3.12.1" from "Python 3.12.1", "22.3.0" from
+/// "v22.3.0", or "1.22.1" from "go version go1.22.1 linux/amd64".
+pub fn parse_version(line: &str) -> Option<String> {
+    line.split_whitespace().find_map(|word| {
+        let version = word.trim_start_matches("go").trim_start_matches('v');
+        version
+            .starts_with(|c: char| c.is_ascii_digit())
+            .then(|| version.trim_end_matches([',', ';']).to_string())
+    })
+}
+
 pub fn run_child_cmd(cmd: &str) -> PEResult {
     if consts::OS == "linux" {
         let status = Command::new("sh").arg("-c").arg(cmd).status();
@@ -53,8 +91,82 @@ pub fn run_child_cmd(cmd: &str) -> PEResult {
         }
         Ok(())
     } else {
-        Err(ProgramError::new(format!("OS not supported by CLI")))
+        Err(ProgramError::new("OS not supported by CLI".to_string()))
+    }
+}
+
+/// Makes `command` spawn in its own process group (or console on windows), so it outlives this process
+/// and is not hit by Ctrl-C presses meant for us.
+pub fn detach(command: &mut Command) {
+    #[cfg(unix)]
+    new_process_group(command);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const DETACHED_PROCESS: u32 = 0x00000008;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+        command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+    }
+}
+
+/// Makes `command` spawn as the leader of a new process group, so signals can be sent to it and all its descendants.
+/// Does nothing on windows.
+pub fn new_process_group(command: &mut Command) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
     }
+    #[cfg(not(unix))]
+    let _ = command;
+}
+
+/// Kills `child` along with its whole process group (on unix, `child` must lead its own group), then reaps it.
+pub fn kill_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    // SAFETY: plain `kill` call on a process group we spawned.
+    unsafe {
+        libc::kill(-(child.id() as i32), libc::SIGKILL);
+    }
+    #[cfg(not(unix))]
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Number of stderr lines kept by `tee_stderr_tail`.
+const STDERR_TAIL_LINES: usize = 30;
+
+/// Echoes `stderr` to our own stderr on a separate thread, and returns the last lines of it once it closes.
+pub fn tee_stderr_tail<R: Read + Send + 'static>(stderr: R) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stderr);
+        let mut tail = VecDeque::with_capacity(STDERR_TAIL_LINES);
+        let mut line = Vec::new();
+        while reader
+            .read_until(b'\n', &mut line)
+            .is_ok_and(|read| read > 0)
+        {
+            let _ = io::stderr().write_all(&line);
+            if tail.len() == STDERR_TAIL_LINES {
+                tail.pop_front();
+            }
+            tail.push_back(String::from_utf8_lossy(&line).into_owned());
+            line.clear();
+        }
+        tail.into_iter().collect()
+    })
+}
+
+/// Checks whether something is listening on `port` on localhost, over either IPv4 or IPv6
+/// (dev servers bound to "localhost" may only listen on one of the two).
+pub fn port_answers(port: u16) -> bool {
+    let addrs = [
+        SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+        SocketAddr::from((Ipv6Addr::LOCALHOST, port)),
+    ];
+    addrs
+        .iter()
+        .any(|addr| TcpStream::connect_timeout(addr, Duration::from_millis(200)).is_ok())
 }
 
 pub fn clear_terminal() {
@@ -62,19 +174,97 @@ pub fn clear_terminal() {
     let _ = run_child_cmd("clear");
 }
 
+/// Prompts for one of `options` by number or name, re-prompting until one is given. An empty answer picks the first.
+pub fn prompt_choice<'a>(prompt: &str, options: &[&'a str]) -> PEResult<&'a str> {
+    let listed = options
+        .iter()
+        .enumerate()
+        .map(|(index, option)| format!("{}. {option}", index + 1))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    loop {
+        let answer = prompt_input(format!("{prompt} ({listed}) [{}]: ", options[0]).as_str())?;
+        let answer = answer.trim().to_lowercase();
+        if answer.is_empty() {
+            return Ok(options[0]);
+        }
+
+        let chosen = options
+            .iter()
+            .enumerate()
+            .find(|(index, option)| **option == answer || (index + 1).to_string() == answer);
+        match chosen {
+            Some((_, option)) => return Ok(option),
+            None => red_log(format!("'{answer}' is not one of the options").as_str()),
+        }
+    }
+}
+
+/// Prompts for any number of `options`, comma separated by number or name. An empty answer picks none.
+pub fn prompt_choices<'a>(prompt: &str, options: &[&'a str]) -> PEResult<Vec<&'a str>> {
+    let listed = options
+        .iter()
+        .enumerate()
+        .map(|(index, option)| format!("{}. {option}", index + 1))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    'prompt: loop {
+        let answer = prompt_input(
+            format!("{prompt} ({listed}), comma separated or empty for none: ").as_str(),
+        )?;
+
+        let mut chosen = vec![];
+        for answer in answer.split(',').map(|a| a.trim().to_lowercase()) {
+            if answer.is_empty() {
+                continue;
+            }
+            let option = options
+                .iter()
+                .enumerate()
+                .find(|(index, option)| **option == answer || (index + 1).to_string() == answer);
+            match option {
+                Some((_, option)) if !chosen.contains(option) => chosen.push(*option),
+                Some(_) => (),
+                None => {
+                    red_log(format!("'{answer}' is not one of the options").as_str());
+                    continue 'prompt;
+                }
+            }
+        }
+        return Ok(chosen);
+    }
+}
+
 pub fn prompt_input(prompt: &str) -> PEResult<String> {
     print!("{}", prompt.underline());
     io::stdout()
         .flush()
         .expect("should be able to print buffered text to the console");
-    let mut input = String::new();
-    if let Err(e) = io::stdin().read_line(&mut input) {
-        return Err(ProgramError::new(format!(
-            "failed to read user input: {}",
-            e.kind()
-        )));
-    };
-    Ok(input)
+    // read a byte at a time, as `read_line` retries reads interrupted by a signal, and Ctrl-C has to abort the prompt
+    let mut input = vec![];
+    let mut stdin = io::stdin().lock();
+    let mut byte = [0; 1];
+    loop {
+        match stdin.read(&mut byte) {
+            Ok(0) => break,
+            Ok(_) => {
+                input.push(byte[0]);
+                if byte[0] == b'\n' {
+                    break;
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => signals::check_for_abort()?,
+            Err(e) => {
+                return Err(ProgramError::new(format!(
+                    "failed to read user input: {}",
+                    e.kind()
+                )))
+            }
+        }
+    }
+    Ok(String::from_utf8_lossy(&input).into_owned())
 }
 
 // colored log functions